@@ -34,6 +34,18 @@ fn example_benchmark(c: &mut Criterion) {
             })
         },
     );
+    c.bench_function("for_range! u16 1 ..= 10000 for +=", |b| {
+        b.iter(|| {
+            use rangex::for_range;
+
+            // Code to benchmark
+            let mut s: u16 = 0;
+            for_range!(v in u16: 1 ..= 10000 => {
+                s = s.wrapping_add(v)
+            });
+            s
+        })
+    });
     c.bench_function("std range (1..=10000).sum::<u64>() in black_box", |b| {
         b.iter(|| {
             let _result = black_box((1..=10000).sum::<u64>());
@@ -0,0 +1,178 @@
+//! `#[derive(RangeStep)]` for forangex, so newtypes and fieldless enums can be used in ranges.
+//!
+//! * Single field structs, e.g. `struct UserId(u64)`, step like the field type
+//! * Fieldless enums step through variants in declaration order,
+//!   or in ascending discriminant order with `#[range_step(discriminant)]`,
+//!   explicit discriminants in declaration order must ascend since derived PartialOrd compares them
+//!
+//! The deriving type also needs `Clone, Copy, Debug, PartialEq, PartialOrd`.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields};
+
+#[proc_macro_derive(RangeStep, attributes(range_step))]
+pub fn derive_range_step(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let result = match &input.data {
+        Data::Struct(data) => derive_struct(&input, &data.fields),
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(_) => Err(Error::new(
+            input.span(),
+            "RangeStep can't be derived for unions",
+        )),
+    };
+    result.unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream2, Error> {
+    if fields.len() != 1 {
+        return Err(Error::new(
+            input.span(),
+            "RangeStep can only be derived for structs with exactly one field",
+        ));
+    }
+    let field = fields.iter().next().unwrap();
+    let position = &field.ty;
+    let name = &input.ident;
+    let (to_position, from_position) = match &field.ident {
+        Some(ident) => (quote!(self.#ident), quote!(#name { #ident: position })),
+        None => (quote!(self.0), quote!(#name(position))),
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rangex::basic_range::RangeStep for #name #ty_generics #where_clause {
+            type Position = #position;
+            fn to_position(self) -> #position {
+                #to_position
+            }
+            fn from_position(position: #position) -> Self {
+                #from_position
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &syn::DataEnum) -> Result<TokenStream2, Error> {
+    if data.variants.is_empty() {
+        return Err(Error::new(
+            input.span(),
+            "RangeStep can't be derived for enums without variants",
+        ));
+    }
+    if let Some(variant) = data.variants.iter().find(|v| !v.fields.is_empty()) {
+        return Err(Error::new(
+            variant.span(),
+            "RangeStep can only be derived for fieldless enums",
+        ));
+    }
+    let by_discriminant = by_discriminant(input)?;
+    let name = &input.ident;
+    let variants: Vec<_> = data.variants.iter().map(|v| &v.ident).collect();
+    let count = variants.len();
+
+    if !by_discriminant {
+        let (first, last) = (variants[0], variants[count - 1]);
+        let positions: Vec<_> = (0..count).collect();
+        // PartialOrd follows discriminants, stepping follows declaration order, they must agree
+        let ascending = if data.variants.iter().any(|v| v.discriminant.is_some()) {
+            let (lower, higher) = (&variants[..count - 1], &variants[1..]);
+            let message = format!(
+                "RangeStep for {} needs ascending discriminants in declaration order, \
+                 or #[range_step(discriminant)]",
+                name
+            );
+            quote! {
+                const _: () = assert!(
+                    true #(&& (#name::#lower as i128) < (#name::#higher as i128))*,
+                    #message
+                );
+            }
+        } else {
+            quote!()
+        };
+        return Ok(quote! {
+            #ascending
+
+            impl ::rangex::basic_range::RangeStep for #name {
+                type Position = usize;
+                fn to_position(self) -> usize {
+                    match self {
+                        #(#name::#variants => #positions,)*
+                    }
+                }
+                fn from_position(position: usize) -> Self {
+                    match position {
+                        #(#positions => #name::#variants,)*
+                        _ => panic!("{} has no variant at position {}", stringify!(#name), position),
+                    }
+                }
+                fn first() -> Self {
+                    #name::#first
+                }
+                fn last() -> Self {
+                    #name::#last
+                }
+            }
+        });
+    }
+
+    Ok(quote! {
+        impl ::rangex::basic_range::RangeStep for #name {
+            type Position = usize;
+            fn to_position(self) -> usize {
+                // Number of variants with a lower discriminant
+                let discriminant = self as i128;
+                0 #(+ ((#name::#variants as i128) < discriminant) as usize)*
+            }
+            fn from_position(position: usize) -> Self {
+                // Variants sorted by discriminant at compile time, insertion sort as const
+                const VARIANTS: [#name; #count] = {
+                    let mut variants = [#(#name::#variants),*];
+                    let mut sorted = 1;
+                    while sorted < #count {
+                        let mut index = sorted;
+                        while index > 0 && (variants[index - 1] as i128) > (variants[index] as i128) {
+                            let lower = variants[index];
+                            variants[index] = variants[index - 1];
+                            variants[index - 1] = lower;
+                            index -= 1;
+                        }
+                        sorted += 1;
+                    }
+                    variants
+                };
+                match VARIANTS.get(position) {
+                    Some(variant) => *variant,
+                    None => panic!("{} has no variant at position {}", stringify!(#name), position),
+                }
+            }
+            fn first() -> Self {
+                Self::from_position(0)
+            }
+            fn last() -> Self {
+                Self::from_position(#count - 1)
+            }
+        }
+    })
+}
+
+/// `#[range_step(discriminant)]` orders variants by discriminant instead of declaration order
+fn by_discriminant(input: &DeriveInput) -> Result<bool, Error> {
+    let mut by_discriminant = false;
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|a| a.path().is_ident("range_step"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("discriminant") {
+                by_discriminant = true;
+                Ok(())
+            } else {
+                Err(meta.error("expected `discriminant`"))
+            }
+        })?;
+    }
+    Ok(by_discriminant)
+}
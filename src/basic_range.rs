@@ -1,12 +1,12 @@
+use core::ops::*;
+use num::traits::float::FloatCore;
 use num::{FromPrimitive, Num, One, Zero};
-use std::mem;
-use std::ops::*;
 
 pub const DEBUG_PRINT: bool = false;
 pub const ERROR_PRINT: bool = true;
 
 /// StepOps describes trait for 'step' in 'for' loop
-pub trait StepOps: Num + PartialOrd + Copy + std::fmt::Debug + std::fmt::Display {
+pub trait StepOps: Num + PartialOrd + Copy + core::fmt::Debug + core::fmt::Display {
     /// min value of step
     fn min() -> Self;
     /// max value of step
@@ -19,6 +19,10 @@ pub trait StepOps: Num + PartialOrd + Copy + std::fmt::Debug + std::fmt::Display
     fn floor(self) -> Self {
         self
     }
+    /// round for float, or same for int
+    fn round(self) -> Self {
+        self
+    }
     /// abs value
     fn abs(self) -> Self {
         if self < Self::zero() {
@@ -29,6 +33,13 @@ pub trait StepOps: Num + PartialOrd + Copy + std::fmt::Debug + std::fmt::Display
     }
     // Convert to usize type
     fn to_usize(self) -> usize;
+    // Convert from usize type
+    fn from_usize(value: usize) -> Self;
+    /// Same as to_usize(), None if self is negative or beyond usize
+    fn checked_to_usize(self) -> Option<usize> {
+        let value = self.to_usize();
+        (self >= Self::zero() && Self::from_usize(value) == self).then_some(value)
+    }
 }
 
 impl StepOps for isize {
@@ -41,6 +52,9 @@ impl StepOps for isize {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for i128 {
@@ -53,6 +67,9 @@ impl StepOps for i128 {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for i64 {
@@ -65,6 +82,9 @@ impl StepOps for i64 {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for i32 {
@@ -77,6 +97,9 @@ impl StepOps for i32 {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for i16 {
@@ -89,6 +112,9 @@ impl StepOps for i16 {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for i8 {
@@ -101,6 +127,9 @@ impl StepOps for i8 {
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
 }
 
 impl StepOps for f32 {
@@ -111,12 +140,21 @@ impl StepOps for f32 {
         Self::MAX
     }
     fn floor(self) -> Self {
-        self.floor()
+        FloatCore::floor(self)
+    }
+    fn round(self) -> Self {
+        FloatCore::round(self)
     }
 
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
+    fn checked_to_usize(self) -> Option<usize> {
+        num::ToPrimitive::to_usize(&self)
+    }
 }
 
 impl StepOps for f64 {
@@ -127,12 +165,333 @@ impl StepOps for f64 {
         Self::MAX
     }
     fn floor(self) -> Self {
-        self.floor()
+        FloatCore::floor(self)
+    }
+    fn round(self) -> Self {
+        FloatCore::round(self)
     }
 
     fn to_usize(self) -> usize {
         self as usize
     }
+    fn from_usize(value: usize) -> Self {
+        value as Self
+    }
+    fn checked_to_usize(self) -> Option<usize> {
+        num::ToPrimitive::to_usize(&self)
+    }
+}
+
+/// Signed integer wider than 128 bits, ExtendedStep of u128 and i128
+///
+/// Holds any u128/i128 value, distances between them and one step beyond their bounds,
+/// value is `high * 2^128 + low` in two's complement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExtendedI128 {
+    high: i64,
+    low: u128,
+}
+
+impl ExtendedI128 {
+    pub const fn from_u128(value: u128) -> Self {
+        ExtendedI128 {
+            high: 0,
+            low: value,
+        }
+    }
+    pub const fn from_i128(value: i128) -> Self {
+        ExtendedI128 {
+            high: if value < 0 { -1 } else { 0 },
+            low: value as u128,
+        }
+    }
+    /// Value modulo 2^128, the bits of a wrapped u128
+    pub const fn low_bits(self) -> u128 {
+        self.low
+    }
+    pub const fn to_u128(self) -> Option<u128> {
+        if self.high == 0 {
+            Some(self.low)
+        } else {
+            None
+        }
+    }
+    pub const fn to_i128(self) -> Option<i128> {
+        let low = self.low as i128;
+        if (self.high == 0 && low >= 0) || (self.high == -1 && low < 0) {
+            Some(low)
+        } else {
+            None
+        }
+    }
+    const fn is_negative(self) -> bool {
+        self.high < 0
+    }
+    /// Whether positive, and magnitude modulo 2^128, exact for steps up to u128::MAX either way
+    pub(crate) const fn sign_magnitude(self) -> (bool, u128) {
+        if self.is_negative() {
+            (false, self.low.wrapping_neg())
+        } else {
+            (self.high != 0 || self.low != 0, self.low)
+        }
+    }
+    fn unsigned_abs(self) -> Self {
+        if self.is_negative() {
+            -self
+        } else {
+            self
+        }
+    }
+    fn shl_one(self) -> Self {
+        ExtendedI128 {
+            high: (self.high << 1) | (self.low >> 127) as i64,
+            low: self.low << 1,
+        }
+    }
+    fn shr_one(self) -> Self {
+        ExtendedI128 {
+            high: self.high >> 1,
+            low: (self.low >> 1) | ((self.high as u128) << 127),
+        }
+    }
+    fn bit(self, index: u32) -> bool {
+        if index < 128 {
+            self.low >> index & 1 == 1
+        } else {
+            self.high >> (index - 128) & 1 == 1
+        }
+    }
+    fn bit_len(self) -> u32 {
+        if self.high != 0 {
+            256 - self.high.leading_zeros() - 64
+        } else {
+            128 - self.low.leading_zeros()
+        }
+    }
+    /// Quotient and remainder of non-negative values, by shift and subtract
+    fn div_rem_unsigned(self, divisor: Self) -> (Self, Self) {
+        if divisor == Self::zero() {
+            panic!("attempt to divide by zero");
+        }
+        let (mut quotient, mut remainder) = (Self::zero(), Self::zero());
+        for index in (0..self.bit_len()).rev() {
+            remainder = remainder.shl_one();
+            remainder.low |= self.bit(index) as u128;
+            quotient = quotient.shl_one();
+            if remainder >= divisor {
+                remainder = remainder - divisor;
+                quotient.low |= 1;
+            }
+        }
+        (quotient, remainder)
+    }
+    /// Quotient and remainder truncated toward zero, like built-in integers
+    fn div_rem(self, divisor: Self) -> (Self, Self) {
+        let (quotient, remainder) = self.unsigned_abs().div_rem_unsigned(divisor.unsigned_abs());
+        let quotient = if self.is_negative() != divisor.is_negative() {
+            -quotient
+        } else {
+            quotient
+        };
+        let remainder = if self.is_negative() {
+            -remainder
+        } else {
+            remainder
+        };
+        (quotient, remainder)
+    }
+}
+
+impl From<u128> for ExtendedI128 {
+    fn from(value: u128) -> Self {
+        Self::from_u128(value)
+    }
+}
+
+impl From<i128> for ExtendedI128 {
+    fn from(value: i128) -> Self {
+        Self::from_i128(value)
+    }
+}
+
+/// Smaller integers, so any integer step converts with TryFrom
+macro_rules! impl_extended_i128_from {
+    ($($t:ty => $from:ident, $wide:ty);* $(;)?) => {$(
+        impl From<$t> for ExtendedI128 {
+            fn from(value: $t) -> Self {
+                Self::$from(value as $wide)
+            }
+        }
+    )*};
+}
+
+impl_extended_i128_from! {
+    u8 => from_u128, u128;
+    u16 => from_u128, u128;
+    u32 => from_u128, u128;
+    u64 => from_u128, u128;
+    usize => from_u128, u128;
+    i8 => from_i128, i128;
+    i16 => from_i128, i128;
+    i32 => from_i128, i128;
+    i64 => from_i128, i128;
+    isize => from_i128, i128;
+}
+
+/// Integers the value fits, so extended steps of u128 and i128 convert like primitive ones
+macro_rules! impl_try_from_extended_i128 {
+    ($($t:ty),* $(,)?) => {$(
+        impl TryFrom<ExtendedI128> for $t {
+            type Error = crate::convert_range::OutOfRangeError;
+            fn try_from(value: ExtendedI128) -> Result<Self, Self::Error> {
+                let fits = match (value.to_i128(), value.to_u128()) {
+                    (Some(value), _) => <$t>::from_i128(value),
+                    (None, Some(value)) => <$t>::from_u128(value),
+                    (None, None) => None,
+                };
+                fits.ok_or(crate::convert_range::OutOfRangeError::Step)
+            }
+        }
+    )*};
+}
+
+impl_try_from_extended_i128!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Neg for ExtendedI128 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        let (low, carry) = (!self.low).overflowing_add(1);
+        ExtendedI128 {
+            high: (!self.high).wrapping_add(carry as i64),
+            low,
+        }
+    }
+}
+
+impl Add for ExtendedI128 {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let (low, carry) = self.low.overflowing_add(other.low);
+        ExtendedI128 {
+            high: self.high + other.high + carry as i64,
+            low,
+        }
+    }
+}
+
+impl Sub for ExtendedI128 {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for ExtendedI128 {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let (mut a, mut b) = (self.unsigned_abs(), other.unsigned_abs());
+        if a < b {
+            (a, b) = (b, a);
+        }
+        let mut product = Self::zero();
+        while b != Self::zero() {
+            if b.bit(0) {
+                product = product + a;
+            }
+            a = a.shl_one();
+            b = b.shr_one();
+        }
+        if self.is_negative() != other.is_negative() {
+            -product
+        } else {
+            product
+        }
+    }
+}
+
+impl Div for ExtendedI128 {
+    type Output = Self;
+    fn div(self, other: Self) -> Self {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for ExtendedI128 {
+    type Output = Self;
+    fn rem(self, other: Self) -> Self {
+        self.div_rem(other).1
+    }
+}
+
+impl Zero for ExtendedI128 {
+    fn zero() -> Self {
+        Self::from_u128(0)
+    }
+    fn is_zero(&self) -> bool {
+        *self == Self::zero()
+    }
+}
+
+impl One for ExtendedI128 {
+    fn one() -> Self {
+        Self::from_u128(1)
+    }
+}
+
+impl Num for ExtendedI128 {
+    type FromStrRadixErr = core::num::ParseIntError;
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        match s.strip_prefix('-') {
+            Some(magnitude) => u128::from_str_radix(magnitude, radix).map(|v| -Self::from_u128(v)),
+            None => u128::from_str_radix(s, radix).map(Self::from_u128),
+        }
+    }
+}
+
+impl core::fmt::Display for ExtendedI128 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if let Some(value) = self.to_i128() {
+            return core::fmt::Display::fmt(&value, f);
+        }
+        // At most 2^191, 58 digits
+        let mut digits = [0u8; 58];
+        let mut start = digits.len();
+        let mut magnitude = self.unsigned_abs();
+        let ten = Self::from_u128(10);
+        while magnitude != Self::zero() {
+            let (quotient, digit) = magnitude.div_rem_unsigned(ten);
+            start -= 1;
+            digits[start] = b'0' + digit.low as u8;
+            magnitude = quotient;
+        }
+        let digits = core::str::from_utf8(&digits[start..]).unwrap();
+        f.pad_integral(!self.is_negative(), "", digits)
+    }
+}
+
+impl StepOps for ExtendedI128 {
+    fn min() -> Self {
+        ExtendedI128 {
+            high: i64::MIN,
+            low: 0,
+        }
+    }
+    fn max() -> Self {
+        ExtendedI128 {
+            high: i64::MAX,
+            low: u128::MAX,
+        }
+    }
+    /// Low bits as usize, see checked_to_usize()
+    fn to_usize(self) -> usize {
+        self.low as usize
+    }
+    fn from_usize(value: usize) -> Self {
+        Self::from_u128(value as u128)
+    }
+    fn checked_to_usize(self) -> Option<usize> {
+        self.to_u128().and_then(|value| usize::try_from(value).ok())
+    }
 }
 
 // #![feature(f128_type)]
@@ -144,7 +503,7 @@ impl StepOps for f64 {
 // }
 
 /// IteratorOps describes trait for 'from'/'to' in 'for' loop
-pub trait IteratorOps: Num + PartialOrd + Copy + std::fmt::Debug + std::fmt::Display {
+pub trait IteratorOps: PartialOrd + Copy + core::fmt::Debug {
     /// Step is the type for 'step', signed counterpart of 'from'/'to
     type Step: StepOps;
     type ExtendedStep: StepOps;
@@ -155,25 +514,45 @@ pub trait IteratorOps: Num + PartialOrd + Copy + std::fmt::Debug + std::fmt::Dis
     fn to_extended_step(self) -> Self::ExtendedStep;
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self;
     fn extend_step(step: Self::Step) -> Self::ExtendedStep;
+    /// Whether adding the step repeatedly lands exactly on start + index * step,
+    /// false for floats where every addition rounds
+    const EXACT_STEPS: bool = true;
     //fn next(&mut self, step: Self::Step) { *self = *self + Self::from_step(step); }
     fn next(&mut self, step: Self::Step) {
         *self = Self::from_extended_step(self.to_extended_step() + Self::extend_step(step));
     }
+    /// Value one step further, wrapping around at type bounds, like next() but for hot loops
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        let mut value = self;
+        value.next(step);
+        value
+    }
 }
 
-pub trait SizeCompatible<T> {}
+/// Types with the same width as T, reinterpreted bit for bit, e.g. -1i8 is 255u8
+pub trait SizeCompatible<T> {
+    fn reinterpret(self) -> T;
+}
 
 // Implement `SizeCompatible` only for types with the same size.
-impl SizeCompatible<u8> for i8 {}
-impl SizeCompatible<i8> for i8 {}
-impl SizeCompatible<u16> for i16 {}
-impl SizeCompatible<i16> for i16 {}
-impl SizeCompatible<u32> for i32 {}
-impl SizeCompatible<i32> for i32 {}
-impl SizeCompatible<u64> for i64 {}
-impl SizeCompatible<i64> for i64 {}
-impl SizeCompatible<u128> for i128 {}
-impl SizeCompatible<i128> for i128 {}
+macro_rules! impl_size_compatible {
+    ($($from:ty => $($to:ty),*);* $(;)?) => {$($(
+        impl SizeCompatible<$to> for $from {
+            fn reinterpret(self) -> $to {
+                self as $to
+            }
+        }
+    )*)*};
+}
+
+impl_size_compatible! {
+    i8 => u8, i8;
+    i16 => u16, i16;
+    i32 => u32, i32;
+    i64 => u64, i64;
+    i128 => u128, i128;
+    isize => usize, isize;
+}
 
 // Implement the trait for specific types
 impl IteratorOps for u8 {
@@ -188,11 +567,11 @@ impl IteratorOps for u8 {
     }
 
     fn to_step(self) -> i8 {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
 
     fn from_step(step: i8) -> u8 {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> i16 {
@@ -201,7 +580,7 @@ impl IteratorOps for u8 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i16(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -213,9 +592,11 @@ impl IteratorOps for u8 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -224,6 +605,10 @@ impl IteratorOps for u8 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
+    }
 }
 
 impl IteratorOps for i8 {
@@ -250,7 +635,7 @@ impl IteratorOps for i8 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i16(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -262,9 +647,11 @@ impl IteratorOps for i8 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -273,6 +660,10 @@ impl IteratorOps for i8 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
+    }
 }
 
 impl IteratorOps for u16 {
@@ -287,10 +678,10 @@ impl IteratorOps for u16 {
     }
 
     fn to_step(self) -> Self::Step {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
@@ -299,7 +690,7 @@ impl IteratorOps for u16 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i32(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -311,9 +702,11 @@ impl IteratorOps for u16 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -322,6 +715,10 @@ impl IteratorOps for u16 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
+    }
 }
 
 impl IteratorOps for i16 {
@@ -348,7 +745,7 @@ impl IteratorOps for i16 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i32(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -360,9 +757,11 @@ impl IteratorOps for i16 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -371,6 +770,10 @@ impl IteratorOps for i16 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
+    }
 }
 
 impl IteratorOps for u32 {
@@ -385,10 +788,10 @@ impl IteratorOps for u32 {
     }
 
     fn to_step(self) -> Self::Step {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
@@ -397,7 +800,7 @@ impl IteratorOps for u32 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i64(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -409,9 +812,11 @@ impl IteratorOps for u32 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -420,6 +825,10 @@ impl IteratorOps for u32 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
+    }
 }
 
 impl IteratorOps for i32 {
@@ -446,7 +855,7 @@ impl IteratorOps for i32 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i64(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -458,9 +867,11 @@ impl IteratorOps for i32 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -469,6 +880,10 @@ impl IteratorOps for i32 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
+    }
 }
 
 impl IteratorOps for u64 {
@@ -483,10 +898,10 @@ impl IteratorOps for u64 {
     }
 
     fn to_step(self) -> Self::Step {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
@@ -495,7 +910,7 @@ impl IteratorOps for u64 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i128(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -507,9 +922,11 @@ impl IteratorOps for u64 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -518,6 +935,10 @@ impl IteratorOps for u64 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
+    }
 }
 
 impl IteratorOps for i64 {
@@ -544,7 +965,7 @@ impl IteratorOps for i64 {
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
         if let Some(result) = Self::from_i128(extended_step) {
             if DEBUG_PRINT {
-                println!("From extended step {} {}", extended_step, result);
+                log_print!("From extended step {} {}", extended_step, result);
             }
             result
         } else {
@@ -556,9 +977,11 @@ impl IteratorOps for i64 {
                 (self_range_inclusive_number + extended_step) as Self
             };
             if DEBUG_PRINT || ERROR_PRINT {
-                println!(
+                log_print!(
                     "From extended step overflow {} adjust with {} as {}",
-                    extended_step, self_range_inclusive_number, result
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
                 );
             }
             result
@@ -567,11 +990,15 @@ impl IteratorOps for i64 {
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
+    }
 }
 
 impl IteratorOps for u128 {
     type Step = i128;
-    type ExtendedStep = i128;
+    type ExtendedStep = ExtendedI128;
 
     fn min() -> Self {
         u128::MIN
@@ -581,55 +1008,95 @@ impl IteratorOps for u128 {
     }
 
     fn to_step(self) -> Self::Step {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
-        unsafe { mem::transmute(self) }
+        ExtendedI128::from_u128(self)
     }
-    fn from_extended_step(step: Self::ExtendedStep) -> Self {
-        unsafe { mem::transmute(step) }
+    fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
+        if let Some(result) = extended_step.to_u128() {
+            if DEBUG_PRINT {
+                log_print!("From extended step {} {}", extended_step, result);
+            }
+            result
+        } else {
+            // Two's complement, low 128 bits are the value wrapped around type bounds
+            let result = extended_step.low_bits() as Self;
+            if DEBUG_PRINT || ERROR_PRINT {
+                log_print!(
+                    "From extended step overflow {} wrap as {}",
+                    extended_step,
+                    result
+                );
+            }
+            result
+        }
     }
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
-        step as Self::ExtendedStep
+        ExtendedI128::from_i128(step)
+    }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
     }
 }
 
 impl IteratorOps for i128 {
     type Step = i128;
-    type ExtendedStep = i128;
+    type ExtendedStep = ExtendedI128;
 
-    fn min() -> i128 {
+    fn min() -> Self {
         i128::MIN
     }
-    fn max() -> i128 {
+    fn max() -> Self {
         i128::MAX
     }
 
     fn to_step(self) -> Self::Step {
-        self
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        step
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
-        self
+        ExtendedI128::from_i128(self)
     }
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
-        extended_step
+        if let Some(result) = extended_step.to_i128() {
+            if DEBUG_PRINT {
+                log_print!("From extended step {} {}", extended_step, result);
+            }
+            result
+        } else {
+            // Two's complement, low 128 bits are the value wrapped around type bounds
+            let result = extended_step.low_bits() as Self;
+            if DEBUG_PRINT || ERROR_PRINT {
+                log_print!(
+                    "From extended step overflow {} wrap as {}",
+                    extended_step,
+                    result
+                );
+            }
+            result
+        }
     }
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
-        step as Self::ExtendedStep
+        ExtendedI128::from_i128(step)
+    }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
     }
 }
 
 impl IteratorOps for usize {
     type Step = isize;
-    type ExtendedStep = isize;
+    type ExtendedStep = i128;
 
     fn min() -> Self {
         usize::MIN
@@ -639,32 +1106,57 @@ impl IteratorOps for usize {
     }
 
     fn to_step(self) -> Self::Step {
-        unsafe { mem::transmute(self) }
+        self as Self::Step
     }
     fn from_step(step: Self::Step) -> Self {
-        unsafe { mem::transmute(step) }
+        step as Self
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
-        unsafe { mem::transmute(self) }
+        self as Self::ExtendedStep
     }
-    fn from_extended_step(step: Self::ExtendedStep) -> Self {
-        unsafe { mem::transmute(step) }
+    fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
+        if let Some(result) = Self::from_i128(extended_step) {
+            if DEBUG_PRINT {
+                log_print!("From extended step {} {}", extended_step, result);
+            }
+            result
+        } else {
+            let self_range_inclusive_number =
+                1 + Self::MAX as Self::ExtendedStep - Self::MIN as Self::ExtendedStep;
+            let result = if extended_step > Self::MAX as Self::ExtendedStep {
+                (extended_step - self_range_inclusive_number) as Self
+            } else {
+                (self_range_inclusive_number + extended_step) as Self
+            };
+            if DEBUG_PRINT || ERROR_PRINT {
+                log_print!(
+                    "From extended step overflow {} adjust with {} as {}",
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
+                );
+            }
+            result
+        }
     }
-
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add_signed(step)
+    }
 }
 
 impl IteratorOps for isize {
     type Step = isize;
-    type ExtendedStep = isize;
+    type ExtendedStep = i128;
 
-    fn min() -> isize {
+    fn min() -> Self {
         isize::MIN
     }
-    fn max() -> isize {
+    fn max() -> Self {
         isize::MAX
     }
 
@@ -676,20 +1168,46 @@ impl IteratorOps for isize {
     }
 
     fn to_extended_step(self) -> Self::ExtendedStep {
-        self
+        self as Self::ExtendedStep
     }
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
-        extended_step
+        if let Some(result) = Self::from_i128(extended_step) {
+            if DEBUG_PRINT {
+                log_print!("From extended step {} {}", extended_step, result);
+            }
+            result
+        } else {
+            let self_range_inclusive_number =
+                1 + Self::MAX as Self::ExtendedStep - Self::MIN as Self::ExtendedStep;
+            let result = if extended_step > Self::MAX as Self::ExtendedStep {
+                (extended_step - self_range_inclusive_number) as Self
+            } else {
+                (self_range_inclusive_number + extended_step) as Self
+            };
+            if DEBUG_PRINT || ERROR_PRINT {
+                log_print!(
+                    "From extended step overflow {} adjust with {} as {}",
+                    extended_step,
+                    self_range_inclusive_number,
+                    result
+                );
+            }
+            result
+        }
     }
-
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
     }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        self.wrapping_add(step)
+    }
 }
 
 impl IteratorOps for f32 {
     type Step = f32;
     type ExtendedStep = f64;
+    const EXACT_STEPS: bool = false;
 
     fn min() -> f32 {
         f32::MIN
@@ -709,11 +1227,7 @@ impl IteratorOps for f32 {
         self as f64
     }
     fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
-        if let Some(result) = f32::from_f64(extended_step) {
-            result
-        } else {
-            0.0
-        }
+        f32::from_f64(extended_step).unwrap_or(0.0)
     }
     fn extend_step(step: Self::Step) -> Self::ExtendedStep {
         step as Self::ExtendedStep
@@ -727,6 +1241,7 @@ impl IteratorOps for f32 {
 impl IteratorOps for f64 {
     type Step = f64;
     type ExtendedStep = f64;
+    const EXACT_STEPS: bool = false;
 
     fn min() -> f64 {
         f64::MIN
@@ -770,44 +1285,235 @@ impl IteratorOps for f64 {
 //     }
 // }
 
-pub struct BasicRange<T>
-where
-    T: IteratorOps,
-{
-    pub start: T,
-    pub end: T,
-    pub step: T::Step,
-
-    pub inclusive_or_not_on_step: bool,
-    pub invalid_range: bool,
+/// `#[derive(RangeStep)]` for single field structs and fieldless enums, with feature `derive`
+#[cfg(feature = "derive")]
+pub use rangex_derive::RangeStep;
+
+/// RangeStep is the minimal trait for user types, e.g. `UserId(u64)` or a fieldless enum,
+/// to get IteratorOps through the blanket impl below.
+///
+/// Each value has a position in a built-in IteratorOps type, distance between values and
+/// advancing by n steps are done on positions, so stepping is the same as for the position type.
+/// ```
+/// use rangex::basic_range::*;
+/// use rangex::range_inclusive;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+/// struct UserId(u64);
+///
+/// impl RangeStep for UserId {
+///     type Position = u64;
+///     fn to_position(self) -> u64 {
+///         self.0
+///     }
+///     fn from_position(position: u64) -> Self {
+///         UserId(position)
+///     }
+/// }
+///
+/// let ids: Vec<UserId> = range_inclusive!(UserId, UserId(1), UserId(7), 3).into_iter().collect();
+/// assert_eq!(ids, [UserId(1), UserId(4), UserId(7)]);
+/// ```
+///
+/// `#[derive(RangeStep)]` rejects enums whose PartialOrd, which follows discriminants,
+/// disagrees with declaration order, unless variants are ordered by `#[range_step(discriminant)]`
+/// ```compile_fail
+/// #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, rangex_derive::RangeStep)]
+/// enum Rev {
+///     A = 2,
+///     B = 1,
+/// }
+/// ```
+pub trait RangeStep: PartialOrd + Copy + core::fmt::Debug {
+    /// Built-in type positions are counted in, step type is its Step
+    type Position: IteratorOps;
+    fn to_position(self) -> Self::Position;
+    /// Only called with positions from first() through last()
+    fn from_position(position: Self::Position) -> Self;
+    /// Lowest value, position type min by default, e.g. first variant for enums
+    fn first() -> Self {
+        Self::from_position(Self::Position::min())
+    }
+    /// Highest value, position type max by default, e.g. last variant for enums
+    fn last() -> Self {
+        Self::from_position(Self::Position::max())
+    }
 }
 
-impl<T> BasicRange<T>
+impl<T> IteratorOps for T
 where
-    T: IteratorOps,
+    T: RangeStep,
 {
-    pub fn new(start: T, mut end: T, step: T::Step, inclusive: bool) -> Self {
-        if step == T::Step::zero() {
-            panic!("Step can't be 0");
-        }
-
-        let mut on_step = true;
-        let invalid_range =
-            (start < end && step < T::Step::zero()) || (start > end && step > T::Step::zero());
+    type Step = <T::Position as IteratorOps>::Step;
+    type ExtendedStep = <T::Position as IteratorOps>::ExtendedStep;
+    const EXACT_STEPS: bool = T::Position::EXACT_STEPS;
 
-        if !invalid_range {
-            let range_size: T::ExtendedStep = if step > T::Step::zero() {
+    fn min() -> Self {
+        T::first()
+    }
+    fn max() -> Self {
+        T::last()
+    }
+
+    fn to_step(self) -> Self::Step {
+        self.to_position().to_step()
+    }
+    fn from_step(step: Self::Step) -> Self {
+        T::from_extended_step(T::Position::from_step(step).to_extended_step())
+    }
+
+    fn to_extended_step(self) -> Self::ExtendedStep {
+        self.to_position().to_extended_step()
+    }
+    fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
+        let (first, last) = (T::first().to_position(), T::last().to_position());
+        if first == T::Position::min() && last == T::Position::max() {
+            // Whole position type, wraps the same way
+            return T::from_position(T::Position::from_extended_step(extended_step));
+        }
+        let (first, last) = (first.to_extended_step(), last.to_extended_step());
+        let size = last - first + Self::ExtendedStep::one();
+        // Wraps around first()/last() any number of times, e.g. steps wider than all values
+        let offset = (extended_step - first) % size;
+        let offset = if offset < Self::ExtendedStep::zero() {
+            offset + size
+        } else {
+            offset
+        };
+        T::from_position(T::Position::from_extended_step(first + offset))
+    }
+    fn extend_step(step: Self::Step) -> Self::ExtendedStep {
+        T::Position::extend_step(step)
+    }
+
+    fn wrapping_next(self, step: Self::Step) -> Self {
+        let (first, last) = (T::first().to_position(), T::last().to_position());
+        if first == T::Position::min() && last == T::Position::max() {
+            // Whole position type, wraps natively like the position
+            return T::from_position(self.to_position().wrapping_next(step));
+        }
+        let mut value = self;
+        value.next(step);
+        value
+    }
+}
+
+/// Surrogates U+D800..=U+DFFF are not chars
+const SURROGATE_START: u32 = 0xD800;
+const SURROGATE_COUNT: u32 = 0x800;
+
+/// chars step through valid scalar values, U+D7FF is one step below U+E000
+impl RangeStep for char {
+    type Position = u32;
+    fn to_position(self) -> u32 {
+        let scalar = self as u32;
+        if scalar < SURROGATE_START {
+            scalar
+        } else {
+            scalar - SURROGATE_COUNT
+        }
+    }
+    fn from_position(position: u32) -> Self {
+        let scalar = if position < SURROGATE_START {
+            position
+        } else {
+            position + SURROGATE_COUNT
+        };
+        char::from_u32(scalar).expect("Position beyond char::MAX")
+    }
+    fn first() -> Self {
+        '\0'
+    }
+    fn last() -> Self {
+        char::MAX
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct BasicRange<T>
+where
+    T: IteratorOps,
+{
+    pub start: T,
+    pub end: T,
+    /// Step as added while iterating, wraps around type bounds for steps wider than Step
+    pub step: T::Step,
+    /// Full signed step, same as step unless created by with_magnitude()
+    pub extended_step: T::ExtendedStep,
+
+    pub inclusive_or_not_on_step: bool,
+    pub invalid_range: bool,
+
+    /// Value at index i is origin + (origin_index + i) * extended_step, origin is start unless
+    /// sliced from a longer range, so float slices yield bit for bit the values of the range
+    pub origin: T,
+    pub origin_index: T::ExtendedStep,
+}
+
+impl<T> BasicRange<T>
+where
+    T: IteratorOps,
+{
+    pub fn new(start: T, end: T, step: T::Step, inclusive: bool) -> Self {
+        if step == T::Step::zero() {
+            panic!("Step can't be 0");
+        }
+        Self::new_with_extended_step(start, end, step, T::extend_step(step), inclusive)
+    }
+
+    /// Range by *magnitude* forward or backward, magnitude may be up to the full width of T,
+    /// e.g. `BasicRange::<u8>::with_magnitude(0, 255, 200, true, true)` yields 0 and 200
+    pub fn with_magnitude(
+        start: T,
+        end: T,
+        magnitude: T::ExtendedStep,
+        forward: bool,
+        inclusive: bool,
+    ) -> Self {
+        if magnitude <= T::ExtendedStep::zero() {
+            panic!("Step magnitude must be positive");
+        }
+        if magnitude > T::max().to_extended_step() - T::min().to_extended_step() {
+            panic!("Step magnitude beyond type width");
+        }
+        let extended_step = if forward {
+            magnitude
+        } else {
+            T::ExtendedStep::zero() - magnitude
+        };
+        // Adding the wrapped step is the same as adding the full step, modulo type width
+        let step = T::from_extended_step(Self::wrap_once(extended_step)).to_step();
+        Self::new_with_extended_step(start, end, step, extended_step, inclusive)
+    }
+
+    fn new_with_extended_step(
+        start: T,
+        mut end: T,
+        step: T::Step,
+        extended_step: T::ExtendedStep,
+        inclusive: bool,
+    ) -> Self {
+        let mut on_step = true;
+        let forward = extended_step > T::ExtendedStep::zero();
+        let invalid_range = (start < end && !forward) || (start > end && forward);
+
+        if !invalid_range {
+            let range_size: T::ExtendedStep = if forward {
                 end.to_extended_step() - start.to_extended_step()
             } else {
                 start.to_extended_step() - end.to_extended_step()
             };
 
             if start != end {
-                if step < T::Step::negative_one() || T::Step::one() < step {
-                    (end, on_step) = Self::calculate_stop_and_steps(start, end, range_size, step);
+                // Only 1 and -1 are always on step, fractional steps like 1/3 may not be
+                if extended_step != T::ExtendedStep::negative_one()
+                    && extended_step != T::ExtendedStep::one()
+                {
+                    (end, on_step) =
+                        Self::calculate_stop_and_steps(start, end, range_size, extended_step);
                     if DEBUG_PRINT {
-                        println!(
-                            "end is {}, zero is {}, step is {}, on_step is {}",
+                        log_print!(
+                            "end is {:?}, zero is {}, step is {}, on_step is {}",
                             end,
                             T::Step::zero(),
                             step,
@@ -821,18 +1527,19 @@ where
 
             if inclusive || !on_step {
                 if DEBUG_PRINT {
-                    println!(
-                        "end is {}, zero is {}, step is {}, on_step is {}",
+                    log_print!(
+                        "end is {:?}, zero is {}, step is {}, on_step is {}",
                         end,
                         T::Step::zero(),
                         step,
                         on_step
                     );
                 }
-                end.next(step);
+                // Wraps around type bounds for ranges up to max(), same as next()
+                end = end.wrapping_next(step);
                 if DEBUG_PRINT {
-                    println!(
-                        "end is {}, zero is {}, step is {}, on_step is {}",
+                    log_print!(
+                        "end is {:?}, zero is {}, step is {}, on_step is {}",
                         end,
                         T::Step::zero(),
                         step,
@@ -842,50 +1549,210 @@ where
             }
         }
         if DEBUG_PRINT {
-            println!(
-                "invalid_range: {}, end: {}, step: {}, inclusive: {}, on_step: {}",
-                invalid_range, end, step, inclusive, on_step
+            log_print!(
+                "invalid_range: {}, end: {:?}, step: {}, inclusive: {}, on_step: {}",
+                invalid_range,
+                end,
+                step,
+                inclusive,
+                on_step
             );
         }
         BasicRange {
             start,
             end,
             step,
+            extended_step,
             inclusive_or_not_on_step: inclusive || !on_step,
             invalid_range,
+            origin: start,
+            origin_index: T::ExtendedStep::zero(),
+        }
+    }
+
+    /// Range of *len* values, at least one, from index *origin_index* of the progression from
+    /// *origin* by *extended_step* on
+    pub(crate) fn from_origin(
+        origin: T,
+        origin_index: T::ExtendedStep,
+        extended_step: T::ExtendedStep,
+        len: T::ExtendedStep,
+    ) -> Self {
+        let mut range = BasicRange {
+            start: origin,
+            end: origin,
+            step: T::from_extended_step(Self::wrap_once(extended_step)).to_step(),
+            extended_step,
+            inclusive_or_not_on_step: true,
+            invalid_range: false,
+            origin,
+            origin_index,
+        };
+        range.start = range.value_at_unchecked(T::ExtendedStep::zero());
+        let last = range.value_at_unchecked(len - T::ExtendedStep::one());
+        // One step past the last value, may wrap around type bounds like new() does
+        range.end = T::from_extended_step(Self::wrap_once(last.to_extended_step() + extended_step));
+        range
+    }
+
+    /// Number of values the range yields, panics if it's beyond usize, e.g. all u64 values,
+    /// see checked_len() and len_extended()
+    pub fn len(&self) -> usize {
+        self.checked_len()
+            .expect("len beyond usize, use checked_len() or len_extended()")
+    }
+
+    /// Same as len(), None if it's beyond usize
+    pub fn checked_len(&self) -> Option<usize> {
+        self.len_extended().checked_to_usize()
+    }
+
+    /// Number of values the range yields, exact for any range, e.g. 2^128 for all u128 values
+    pub fn len_extended(&self) -> T::ExtendedStep {
+        if self.invalid_range {
+            return T::ExtendedStep::zero();
+        }
+        let step = self.extended_step.abs();
+        let distance = |from: T, to: T| (to.to_extended_step() - from.to_extended_step()).abs();
+        if self.inclusive_or_not_on_step {
+            // end is one step past the last value, and may have wrapped around type bounds
+            let last = self.end.to_extended_step() - self.extended_step;
+            let last = T::from_extended_step(Self::wrap_once(last));
+            // Both ends are on step, rounding keeps float ranges from losing a value
+            (distance(self.start, last) / step).round() + T::ExtendedStep::one()
+        } else {
+            (distance(self.start, self.end) / step).round()
         }
     }
 
+    /// *value* brought within min()..=max() if it's at most one type width outside, the
+    /// expected wrap of an end past the bounds, so from_extended_step() reports no overflow
+    fn wrap_once(value: T::ExtendedStep) -> T::ExtendedStep {
+        let (min, max) = (T::min().to_extended_step(), T::max().to_extended_step());
+        let width = max - min + T::ExtendedStep::one();
+        if value > max {
+            value - width
+        } else if value < min {
+            value + width
+        } else {
+            value
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        T::ExtendedStep::zero() == self.len_extended()
+    }
+
+    /// Index of the last value, None for an empty range, panics if it's beyond usize
+    pub fn last_index(&self) -> Option<usize> {
+        let len = self.len_extended();
+        if len == T::ExtendedStep::zero() {
+            return None;
+        }
+        let index = (len - T::ExtendedStep::one()).checked_to_usize();
+        Some(index.expect("last index beyond usize, use len_extended()"))
+    }
+
+    /// Value at *index*, None if index is not below len()
+    pub fn value_at(&self, index: usize) -> Option<T> {
+        // Beyond usize len() is above any index, and T::ExtendedStep holds the index
+        if self.checked_len().is_some_and(|len| index >= len) {
+            return None;
+        }
+        Some(self.value_at_unchecked(T::ExtendedStep::from_usize(index)))
+    }
+
+    /// Same as value_at() for indices beyond usize
+    pub fn value_at_extended(&self, index: T::ExtendedStep) -> Option<T> {
+        (T::ExtendedStep::zero() <= index && index < self.len_extended())
+            .then(|| self.value_at_unchecked(index))
+    }
+
+    /// Value at *index* below len_extended()
+    pub(crate) fn value_at_unchecked(&self, index: T::ExtendedStep) -> T {
+        let index = self.origin_index + index;
+        T::from_extended_step(self.origin.to_extended_step() + index * self.extended_step)
+    }
+
+    /// Value at *index*, given *previous* at index - 1, adds the step where that's exact and
+    /// multiplies for floats, so iteration and for_range! agree with value_at()
+    #[doc(hidden)]
+    #[inline(always)]
+    pub fn next_value(&self, previous: T, index: T::ExtendedStep) -> T {
+        if T::EXACT_STEPS {
+            previous.wrapping_next(self.step)
+        } else {
+            self.value_at_unchecked(index)
+        }
+    }
+
+    /// *index* as T::ExtendedStep, at most len_extended() so it fits small step types too
+    pub(crate) fn clamped_index(&self, index: usize) -> T::ExtendedStep {
+        let len = self.len_extended();
+        match len.checked_to_usize() {
+            Some(len_usize) if index >= len_usize => len,
+            _ => T::ExtendedStep::from_usize(index),
+        }
+    }
+
+    /// First value the range yields
+    pub fn first(&self) -> Option<T> {
+        self.value_at(0)
+    }
+
+    /// Last value the range yields
+    pub fn last(&self) -> Option<T> {
+        let len = self.len_extended();
+        (len != T::ExtendedStep::zero())
+            .then(|| self.value_at_unchecked(len - T::ExtendedStep::one()))
+    }
+
+    /// Whether the range yields *value*, i.e. value_at() gives it for some index
+    pub fn contains(&self, value: T) -> bool {
+        let len = self.len_extended();
+        let offset = value.to_extended_step() - self.origin.to_extended_step();
+        // Float division may land just below the index, so check the next one too
+        let index = (offset / self.extended_step).floor() - self.origin_index;
+        [index, index + T::ExtendedStep::one()]
+            .into_iter()
+            .any(|index| {
+                T::ExtendedStep::zero() <= index
+                    && index < len
+                    && self.value_at_unchecked(index) == value
+            })
+    }
+
     fn calculate_stop_and_steps(
         start: T,
         end: T,
         range_size: T::ExtendedStep,
-        step: T::Step,
+        step: T::ExtendedStep,
     ) -> (T, bool)
     where
         T: IteratorOps,
     {
         if DEBUG_PRINT {
-            println!(
-                "start {} end {} range_size {} step {}",
-                start, end, range_size, step
+            log_print!(
+                "start {:?} end {:?} range_size {} step {}",
+                start,
+                end,
+                range_size,
+                step
             );
         }
         let range_size_as_extended_step = range_size;
-        let positive_step: T::ExtendedStep = if step < T::Step::zero() {
-            T::ExtendedStep::zero() - T::extend_step(step)
-        } else {
-            T::extend_step(step)
-        };
+        let positive_step: T::ExtendedStep = step.abs();
         let steps = (range_size_as_extended_step / positive_step).floor();
         if DEBUG_PRINT {
-            println!(
+            log_print!(
                 "range_size_as_extended_step {} steps {}",
-                range_size_as_extended_step, steps
+                range_size_as_extended_step,
+                steps
             );
         }
-        let on_step = T::ExtendedStep::zero() == range_size_as_extended_step.rem(positive_step);
         let new_range_size = steps * positive_step;
+        // Same as a zero remainder for exact types, but floats like 1.0 % 0.1 leave one
+        let on_step = new_range_size == range_size_as_extended_step;
         let new_end: T::ExtendedStep = start.to_extended_step()
             + if start < end {
                 new_range_size
@@ -893,12 +1760,162 @@ where
                 T::ExtendedStep::zero() - new_range_size
             };
         if DEBUG_PRINT {
-            println!("new end {}", new_end);
+            log_print!("new end {}", new_end);
         }
         (T::from_extended_step(new_end), on_step)
     }
 }
 
+/// Extended step of a Step in const fn
+macro_rules! const_extend_step {
+    (ExtendedI128, $step:expr) => {
+        ExtendedI128::from_i128($step)
+    };
+    ($extended:tt, $step:expr) => {
+        $step as $extended
+    };
+}
+
+/// Direction and magnitude of an extended step in const fn
+macro_rules! const_step_parts {
+    (ExtendedI128, $step:expr) => {
+        $step.sign_magnitude()
+    };
+    ($extended:tt, $step:expr) => {
+        ($step > 0, $step.unsigned_abs() as u128)
+    };
+}
+
+/// const fn counterparts of new(), len(), contains() and value_at() for built-in integer types,
+/// so ranges can be declared in statics and consts, e.g.
+/// `static PORTS: BasicRange<u16> = BasicRange::<u16>::const_new(8000, 8010, 2, true);`
+macro_rules! impl_const_basic_range {
+    ($($t:ty => $unsigned:ty, $extended:tt, $add_step:ident);* $(;)?) => {$(
+        impl BasicRange<$t> {
+            /// Same as new(), panics at compile time for step 0 when used in const
+            pub const fn const_new(
+                start: $t,
+                end: $t,
+                step: <$t as IteratorOps>::Step,
+                inclusive: bool,
+            ) -> Self {
+                assert!(step != 0, "Step can't be 0");
+                let invalid_range = (start < end && step < 0) || (start > end && step > 0);
+                let mut end = end;
+                let mut on_step = true;
+                if !invalid_range {
+                    let step_size = step.unsigned_abs() as u128;
+                    let distance = Self::const_distance(start, end);
+                    on_step = distance.is_multiple_of(step_size);
+                    let range_size = distance - distance % step_size;
+                    end = if start < end {
+                        start.wrapping_add(range_size as $t)
+                    } else {
+                        start.wrapping_sub(range_size as $t)
+                    };
+                    if inclusive || !on_step {
+                        end = end.$add_step(step);
+                    }
+                }
+                BasicRange {
+                    start,
+                    end,
+                    step,
+                    extended_step: const_extend_step!($extended, step),
+                    inclusive_or_not_on_step: inclusive || !on_step,
+                    invalid_range,
+                    origin: start,
+                    origin_index: const_extend_step!($extended, 0),
+                }
+            }
+
+            /// Same as len()
+            pub const fn const_len(&self) -> usize {
+                if self.invalid_range {
+                    return 0;
+                }
+                let (forward, step_size) = self.const_step();
+                if self.inclusive_or_not_on_step {
+                    // end is one step past the last value, and may have wrapped around type bounds
+                    let last = if forward {
+                        self.end.wrapping_sub(step_size as $t)
+                    } else {
+                        self.end.wrapping_add(step_size as $t)
+                    };
+                    (Self::const_distance(self.start, last) / step_size) as usize + 1
+                } else {
+                    (Self::const_distance(self.start, self.end) / step_size) as usize
+                }
+            }
+
+            /// Same as value_at()
+            pub const fn const_value_at(&self, index: usize) -> Option<$t> {
+                if index >= self.const_len() {
+                    return None;
+                }
+                let (forward, step_size) = self.const_step();
+                let offset = (index as u128 * step_size) as $t;
+                Some(if forward {
+                    self.start.wrapping_add(offset)
+                } else {
+                    self.start.wrapping_sub(offset)
+                })
+            }
+
+            /// Same as contains()
+            pub const fn const_contains(&self, value: $t) -> bool {
+                let len = self.const_len();
+                if 0 == len {
+                    return false;
+                }
+                let last = match self.const_value_at(len - 1) {
+                    Some(last) => last,
+                    None => return false,
+                };
+                let (forward, step_size) = self.const_step();
+                let in_bounds = if forward {
+                    self.start <= value && value <= last
+                } else {
+                    last <= value && value <= self.start
+                };
+                in_bounds && Self::const_distance(self.start, value).is_multiple_of(step_size)
+            }
+
+            /// Direction and magnitude of extended_step
+            const fn const_step(&self) -> (bool, u128) {
+                const_step_parts!($extended, self.extended_step)
+            }
+
+            const fn const_distance(from: $t, to: $t) -> u128 {
+                if from <= to {
+                    to.wrapping_sub(from) as $unsigned as u128
+                } else {
+                    from.wrapping_sub(to) as $unsigned as u128
+                }
+            }
+        }
+    )*};
+}
+
+impl_const_basic_range!(
+    u8 => u8, i16, wrapping_add_signed;
+    i8 => u8, i16, wrapping_add;
+    u16 => u16, i32, wrapping_add_signed;
+    i16 => u16, i32, wrapping_add;
+    u32 => u32, i64, wrapping_add_signed;
+    i32 => u32, i64, wrapping_add;
+    u64 => u64, i128, wrapping_add_signed;
+    i64 => u64, i128, wrapping_add;
+    u128 => u128, ExtendedI128, wrapping_add_signed;
+    i128 => u128, ExtendedI128, wrapping_add;
+    usize => usize, i128, wrapping_add_signed;
+    isize => usize, i128, wrapping_add;
+);
+
+/// Counts values up to len_extended() rather than comparing to end, so iteration yields
+/// exactly the values of value_at(), also for float steps like 0.1 that never land on end,
+/// the pub fields describe the range and *current* is the next value
+#[derive(Clone, Debug)]
 pub struct BasicRangeIter<T>
 where
     T: IteratorOps,
@@ -909,6 +1926,11 @@ where
 
     pub inclusive_or_not_on_step: bool,
     pub invalid_range: bool,
+
+    range: BasicRange<T>,
+    /// Index of *current*
+    index: T::ExtendedStep,
+    len: T::ExtendedStep,
 }
 
 impl<T> Iterator for BasicRangeIter<T>
@@ -919,52 +1941,28 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         if DEBUG_PRINT {
-            println!(
-                "Current {}, step {}, end {}",
-                self.current, self.step, self.end
+            log_print!(
+                "Current {:?}, step {}, index {}, len {}",
+                self.current,
+                self.step,
+                self.index,
+                self.len
             );
         }
-        if self.invalid_range {
-            None
-        } else {
-            if DEBUG_PRINT {
-                println!(
-                    "Current {}, step {}, end {} inclusive or not on step {}",
-                    self.current, self.step, self.end, self.inclusive_or_not_on_step
-                );
-            }
-            let stop = self.current == self.end;
-
-            if !self.inclusive_or_not_on_step {
-                if stop {
-                    if DEBUG_PRINT {
-                        println!("Stop!");
-                    }
-                    return None;
-                }
-            } else {
-                self.inclusive_or_not_on_step = false;
-            }
+        if self.index == self.len {
+            return None;
+        }
 
-            // Zig lesson
-            // const result = @addWithOverflow(@as(SignedT, @bitCast(self.curr)), self.step);
-            // self.curr = @as(T, @bitCast(result[0]));
+        // Zig lesson
+        // const result = @addWithOverflow(@as(SignedT, @bitCast(self.curr)), self.step);
+        // self.curr = @as(T, @bitCast(result[0]));
 
-            let result = self.current;
-            self.current =
-                T::from_extended_step(self.current.to_extended_step() + T::extend_step(self.step));
-            if DEBUG_PRINT {
-                println!(
-                    "Current {}, step {}, end {}, current + step {}/{}",
-                    result,
-                    self.step,
-                    self.end,
-                    result.to_extended_step() + T::extend_step(self.step),
-                    self.current
-                );
-            }
-            Some(result)
+        let result = self.current;
+        self.index = self.index + T::ExtendedStep::one();
+        if self.index < self.len {
+            self.current = self.range.next_value(result, self.index);
         }
+        Some(result)
     }
 }
 
@@ -977,12 +1975,16 @@ where
 
     fn into_iter(self) -> Self::IntoIter {
         BasicRangeIter {
-            current: self.start,
+            current: self.first().unwrap_or(self.start),
             end: self.end,
             step: self.step,
 
             inclusive_or_not_on_step: self.inclusive_or_not_on_step,
             invalid_range: self.invalid_range,
+
+            range: self,
+            index: T::ExtendedStep::zero(),
+            len: self.len_extended(),
         }
     }
 }
@@ -1013,6 +2015,225 @@ macro_rules! range_inclusive {
     };
 }
 
+/// Same as `range_exclusive!` but the type is inferred from the arguments, and the result is
+/// `BasicRange` whether or not a step is given, so adding a step never changes behavior
+/// ```
+/// use rangex::rangex_exclusive;
+/// assert_eq!(rangex_exclusive!(3, 0).into_iter().count(), 0);
+/// assert_eq!(rangex_exclusive!(3, 0, -1).into_iter().collect::<Vec<_>>(), [3, 2, 1]);
+/// ```
+#[macro_export]
+macro_rules! rangex_exclusive {
+    ($start:expr, $end:expr) => {
+        $crate::rangex_exclusive!($start, $end, 1)
+    };
+    ($start:expr, $end:expr, $step:expr) => {
+        $crate::basic_range::BasicRange::new($start, $end, $step, false)
+    };
+}
+
+/// Same as `range_inclusive!` but the type is inferred from the arguments, and the result is
+/// `BasicRange` whether or not a step is given, so adding a step never changes behavior
+/// ```
+/// use rangex::rangex_inclusive;
+/// assert_eq!(rangex_inclusive!(3, 1).into_iter().count(), 0);
+/// assert_eq!(rangex_inclusive!(3, 1, -1).into_iter().collect::<Vec<_>>(), [3, 2, 1]);
+/// ```
+#[macro_export]
+macro_rules! rangex_inclusive {
+    ($start:expr, $end:expr) => {
+        $crate::rangex_inclusive!($start, $end, 1)
+    };
+    ($start:expr, $end:expr, $step:expr) => {
+        $crate::basic_range::BasicRange::new($start, $end, $step, true)
+    };
+}
+
+/// Range DSL spelling out direction and whether *end* is included
+///
+/// * `rangex!(0 ..= 100)` counts up through *end*, `rangex!(0 .. 100)` counts up until *end*
+/// * `rangex!(100 downto 0)` counts down through *end*, `rangex!(100 downuntil 0)` counts down until *end*
+/// * `rangex!(0 ..= 100, step 5)` or `rangex!(100 downto 0 by 3)` sets the step magnitude,
+///   its sign follows the direction
+/// * `rangex!(u8: 1 .. 10)` names the value type instead of inferring it
+/// * `rangex!(indexed 0 ..= 9)` creates an `IndexedRange` instead of a `BasicRange`
+///
+/// ```
+/// use rangex::rangex;
+/// let v: Vec<u8> = rangex!(u8: 100 downto 0 by 30).into_iter().collect();
+/// assert_eq!(v, [100, 70, 40, 10]);
+/// ```
+///
+/// A literal step of 0 (or below), and literal bounds running against the direction,
+/// are rejected at compile time:
+/// ```compile_fail
+/// let _ = rangex::rangex!(0 ..= 100, step 0);
+/// ```
+/// ```compile_fail
+/// let _ = rangex::rangex!(0 downto 100);
+/// ```
+/// ```compile_fail
+/// let _ = rangex::rangex!(0 downuntil 100);
+/// ```
+#[macro_export]
+macro_rules! rangex {
+    (indexed $($rest:tt)+) => {
+        $crate::rangex!(@type [$crate::indexed_range::IndexedRange] $($rest)+)
+    };
+
+    (@type [$($kind:tt)+] $typename:ident : $($rest:tt)+) => {
+        $crate::rangex!(@start [$($kind)+] [$typename] [] $($rest)+)
+    };
+    (@type [$($kind:tt)+] $($rest:tt)+) => {
+        $crate::rangex!(@start [$($kind)+] [] [] $($rest)+)
+    };
+
+    (@start $kind:tt $typename:tt [$($start:tt)+] ..= $($rest:tt)+) => {
+        $crate::rangex!(@end $kind $typename [$($start)+] [up true] [] $($rest)+)
+    };
+    (@start $kind:tt $typename:tt [$($start:tt)+] .. $($rest:tt)+) => {
+        $crate::rangex!(@end $kind $typename [$($start)+] [up false] [] $($rest)+)
+    };
+    (@start $kind:tt $typename:tt [$($start:tt)+] downto $($rest:tt)+) => {
+        $crate::rangex!(@end $kind $typename [$($start)+] [down true] [] $($rest)+)
+    };
+    (@start $kind:tt $typename:tt [$($start:tt)+] downuntil $($rest:tt)+) => {
+        $crate::rangex!(@end $kind $typename [$($start)+] [down false] [] $($rest)+)
+    };
+    (@start $kind:tt $typename:tt [$($start:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::rangex!(@start $kind $typename [$($start)* $next] $($rest)*)
+    };
+
+    (@end $kind:tt $typename:tt $start:tt $dir:tt [$($end:tt)+] $(,)? step $($step:tt)+) => {
+        $crate::rangex!(@emit $kind $typename $start $dir [$($end)+] [$($step)+])
+    };
+    (@end $kind:tt $typename:tt $start:tt $dir:tt [$($end:tt)+] $(,)? by $($step:tt)+) => {
+        $crate::rangex!(@emit $kind $typename $start $dir [$($end)+] [$($step)+])
+    };
+    (@end $kind:tt $typename:tt $start:tt $dir:tt [$($end:tt)+]) => {
+        $crate::rangex!(@emit $kind $typename $start $dir [$($end)+] [1])
+    };
+    (@end $kind:tt $typename:tt $start:tt $dir:tt [$($end:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::rangex!(@end $kind $typename $start $dir [$($end)* $next] $($rest)*)
+    };
+
+    (@emit [$($kind:tt)+] [$($typename:ident)?] [$($start:tt)+] [$dir:ident $inclusive:tt] [$($end:tt)+] [$($step:tt)+]) => {{
+        $crate::rangex!(@check_step [$($typename)?] [$($step)+]);
+        $crate::rangex!(@check_bounds $dir [$($typename)?] [$($start)+] [$($end)+]);
+        $($kind)+ $(::<$typename>)? ::new(
+            $($start)+,
+            $($end)+,
+            $crate::rangex!(@signed_step $dir $($step)+),
+            $inclusive,
+        )
+    }};
+
+    (@signed_step up $($step:tt)+) => {
+        $($step)+
+    };
+    (@signed_step down $($step:tt)+) => {
+        -($($step)+)
+    };
+
+    // Checks only apply to literals, `-` is peeled off first so `$x:literal` never sees `-n`
+    (@check_step $typename:tt [- $step:tt]) => {
+        $crate::rangex!(@check_step_literal $typename [- $step] $step)
+    };
+    (@check_step $typename:tt [$step:tt]) => {
+        $crate::rangex!(@check_step_literal $typename [$step] $step)
+    };
+    (@check_step $typename:tt [$($step:tt)+]) => {};
+    (@check_step_literal [$($typename:ident)?] [$($step:tt)+] $literal:literal) => {
+        const {
+            let step $(: <$typename as $crate::basic_range::IteratorOps>::Step)? = $($step)+;
+            assert!(
+                step > step - step,
+                "rangex!: step must be a positive magnitude, direction comes from `..`, `..=` or `downto`"
+            );
+        }
+    };
+    (@check_step_literal $typename:tt $step:tt $other:tt) => {};
+
+    (@check_bounds $dir:ident $typename:tt [- $start:tt] [- $end:tt]) => {
+        $crate::rangex!(@check_bounds_literal $dir $typename [- $start] [- $end] $start $end)
+    };
+    (@check_bounds $dir:ident $typename:tt [- $start:tt] [$end:tt]) => {
+        $crate::rangex!(@check_bounds_literal $dir $typename [- $start] [$end] $start $end)
+    };
+    (@check_bounds $dir:ident $typename:tt [$start:tt] [- $end:tt]) => {
+        $crate::rangex!(@check_bounds_literal $dir $typename [$start] [- $end] $start $end)
+    };
+    (@check_bounds $dir:ident $typename:tt [$start:tt] [$end:tt]) => {
+        $crate::rangex!(@check_bounds_literal $dir $typename [$start] [$end] $start $end)
+    };
+    (@check_bounds $dir:ident $typename:tt $start:tt $end:tt) => {};
+    (@check_bounds_literal up [$($typename:ident)?] [$($start:tt)+] [$($end:tt)+] $s:literal $e:literal) => {
+        const {
+            let (start, end) $(: ($typename, $typename))? = ($($start)+, $($end)+);
+            assert!(start <= end, "rangex!: start is above end, use `downto` to count down");
+        }
+    };
+    (@check_bounds_literal down [$($typename:ident)?] [$($start:tt)+] [$($end:tt)+] $s:literal $e:literal) => {
+        const {
+            let (start, end) $(: ($typename, $typename))? = ($($start)+, $($end)+);
+            assert!(start >= end, "rangex!: start is below end, use `..` or `..=` to count up");
+        }
+    };
+    (@check_bounds_literal $dir:ident $typename:tt $start:tt $end:tt $s:tt $e:tt) => {};
+
+    ($($rest:tt)+) => {
+        $crate::rangex!(@type [$crate::basic_range::BasicRange] $($rest)+)
+    };
+}
+
+/// Loop over a `rangex!` range without going through the iterator, the trip count is
+/// computed once and the loop variable is advanced with wrapping arithmetic, or from the index
+/// for floats, like value_at()
+///
+/// `for_range!(i in u32: 0 ..= n, step 4 => { ... })` visits the same values as
+/// `for i in rangex!(u32: 0 ..= n, step 4) { ... }`, `break` and `continue` work as usual,
+/// and a label can be given as `for_range!('outer: i in ... => { ... })`,
+/// panics if the last index is beyond usize, e.g. all u128 values
+/// ```
+/// use rangex::for_range;
+/// let mut s = 0;
+/// for_range!(v in u16: 1 ..= 100 => {
+///     s += v;
+/// });
+/// assert_eq!(s, 5050);
+/// ```
+#[macro_export]
+macro_rules! for_range {
+    (@munch $label:tt $var:tt [$($range:tt)+] => $body:block) => {
+        $crate::for_range!(@emit $label $var [$($range)+] $body)
+    };
+    (@munch $label:tt $var:tt [$($range:tt)*] $next:tt $($rest:tt)+) => {
+        $crate::for_range!(@munch $label $var [$($range)* $next] $($rest)+)
+    };
+    (@emit [$($label:lifetime)?] [$var:pat] [$($range:tt)+] $body:block) => {{
+        let range = $crate::rangex!($($range)+);
+        let mut value = range.start;
+        let mut index = 0usize;
+        // Values after the current one, so all u64 values still fit usize
+        let mut remaining = range.last_index();
+        $($label:)? while let Some(after) = remaining {
+            let $var = value;
+            remaining = after.checked_sub(1);
+            // Wraps only after the last value, e.g. all u64 values
+            index = index.wrapping_add(1);
+            value = range.next_value(value, $crate::basic_range::StepOps::from_usize(index));
+            $body
+        }
+    }};
+
+    ($label:lifetime : $var:pat in $($rest:tt)+) => {
+        $crate::for_range!(@munch [$label] [$var] [] $($rest)+)
+    };
+    ($var:pat in $($rest:tt)+) => {
+        $crate::for_range!(@munch [] [$var] [] $($rest)+)
+    };
+}
+
 #[cfg(test)]
 mod main_test {
     use super::*;
@@ -1098,6 +2319,557 @@ mod main_test {
     }
     //}
 
+    #[test]
+    fn rangex_dsl() {
+        verify_range(vec![0, 5, 10], rangex!(0 ..= 10, step 5));
+        verify_range(vec![0, 5], rangex!(0 .. 10, step 5));
+        verify_range(vec![0, 1, 2], rangex!(0..3));
+        verify_range(vec![100, 97, 94], rangex!(100 downto 94 by 3));
+        verify_range(vec![100, 97], rangex!(100 downto 95, by 3));
+        verify_range(vec![100, 97], rangex!(100 downuntil 94 by 3));
+        verify_range(vec![3, 2, 1], rangex!(3 downuntil 0));
+        verify_range(vec![1u8, 4, 7], rangex!(u8: 1 .. 10 step 3));
+        verify_range(vec![255u8, 128, 1], rangex!(u8: 255 downto 0 by 127));
+        verify_range(vec![0.0, 0.5, 1.0], rangex!(0.0 ..= 1.0, step 0.5));
+
+        let (n, step) = (4, 2);
+        verify_range(vec![-4, -2, 0, 2, 4], rangex!(-n ..= n, step step));
+        verify_range(vec![3, 1], rangex!(n - 1 downto 0 by step));
+        // Direction mismatch with runtime values is an empty range, same as BasicRange::new
+        verify_range(vec![], rangex!(n..=0));
+    }
+
+    #[test]
+    fn rangex_inferred() {
+        verify_range(vec![0, 1, 2], rangex_exclusive!(0, 3));
+        verify_range(vec![0, 2], rangex_exclusive!(0, 3, 2));
+        verify_range(vec![0, 1, 2], rangex_inclusive!(0, 2));
+        verify_range(vec![0, 2], rangex_inclusive!(0, 2, 2));
+        verify_range(vec![1u8, 2], rangex_inclusive!(1u8, 2));
+
+        // std 3..=1 is empty, so is the step 1 form, only the explicit -1 counts down
+        let (start, end) = (3, 1);
+        verify_std_range(vec![], range_inclusive!(start, end));
+        verify_range(vec![], rangex_inclusive!(start, end));
+        verify_range(vec![3, 2, 1], rangex_inclusive!(start, end, -1));
+        verify_range(vec![3, 2], rangex_exclusive!(start, end, -1));
+    }
+
+    #[test]
+    fn len() {
+        assert_eq!(BasicRange::new(0, 3, 1, false).len(), 3);
+        assert_eq!(BasicRange::new(0, 5, 2, false).len(), 3);
+        assert_eq!(BasicRange::new(5, 2, -2, true).len(), 2);
+        assert_eq!(BasicRange::new(0, 0, 1, true).len(), 1);
+        assert!(BasicRange::new(0, 0, 1, false).is_empty());
+        assert!(BasicRange::new(3, 0, 1, true).is_empty());
+        assert_eq!(BasicRange::<u8>::new(0, 255, 1, true).len(), 256);
+        assert_eq!(BasicRange::<u8>::new(255, 0, -5, true).len(), 52);
+        assert_eq!(BasicRange::new(0.0, 5.0, 2.0, true).len(), 3);
+        // Fractional step with end not on step
+        assert_eq!(BasicRange::new(0.0, 1.0, 0.375, false).len(), 3);
+        assert_eq!(
+            BasicRange::new(0.0, 1.0, 0.375, false).into_iter().count(),
+            3
+        );
+        // Steps not exact in binary, adding them never lands on end
+        for (start, end, step, inclusive, len) in [
+            (0.0, 1.0, 0.1, true, 11),
+            (0.0, 1.0, 0.1, false, 10),
+            (1.0, 0.0, -0.1, true, 11),
+            (0.0, 3.0, 0.3, true, 11),
+            (0.0, 10.0, 0.1, true, 101),
+        ] {
+            let r = BasicRange::<f64>::new(start, end, step, inclusive);
+            assert_eq!(r.len(), len);
+            assert_eq!(r.into_iter().count(), len);
+        }
+    }
+
+    fn verify_float_values<T>(r: BasicRange<T>)
+    where
+        T: IteratorOps,
+    {
+        let values: Vec<T> = r.into_iter().collect();
+        assert_eq!(values.len(), r.len());
+        for (index, value) in values.iter().enumerate() {
+            assert_eq!(r.value_at(index), Some(*value));
+            assert!(r.contains(*value));
+        }
+        assert_eq!(values.last().copied(), r.last());
+    }
+
+    #[test]
+    fn float_values_agree() {
+        verify_float_values(BasicRange::<f64>::new(0.0, 10.0, 0.1, true));
+        verify_float_values(BasicRange::<f64>::new(0.0, 10.0, 0.1, false));
+        verify_float_values(BasicRange::<f64>::new(10.0, 0.0, -0.1, true));
+        verify_float_values(BasicRange::<f64>::new(-3.0, 3.0, 0.3, true));
+        verify_float_values(BasicRange::<f64>::new(1e6, 2e6, 977.3, false));
+        verify_float_values(BasicRange::<f32>::new(0.0, 10.0, 0.1, true));
+
+        let r = BasicRange::<f64>::new(0.0, 10.0, 0.1, true);
+        assert_eq!(r.into_iter().nth(30), Some(3.0));
+        assert!(!r.contains(0.05));
+        assert!(!r.contains(-0.1));
+        assert!(!r.contains(10.1));
+        assert!(!BasicRange::<f64>::new(0.0, 10.0, 0.1, false).contains(10.0));
+    }
+
+    fn verify_for_range<T>(start: T, end: T, step: T::Step, inclusive: bool)
+    where
+        T: IteratorOps + std::ops::Neg<Output = T>,
+        T::Step: std::ops::Neg<Output = T::Step>,
+    {
+        let expect: Vec<T> = BasicRange::new(start, end, step, inclusive)
+            .into_iter()
+            .collect();
+        let mut values = Vec::new();
+        if step > T::Step::zero() {
+            if inclusive {
+                for_range!(v in start ..= end, step step => { values.push(v); });
+            } else {
+                for_range!(v in start .. end, step step => { values.push(v); });
+            }
+        } else if inclusive {
+            for_range!(v in start downto end, by -step => { values.push(v); });
+        } else {
+            for_range!(v in start downuntil end, by -step => { values.push(v); });
+        }
+        assert_eq!(values, expect);
+    }
+
+    #[test]
+    fn for_range_same_as_basic_range() {
+        for inclusive in [false, true] {
+            verify_for_range(0, 3, 1, inclusive);
+            verify_for_range(0, 5, 2, inclusive);
+            verify_for_range(0, 0, 2, inclusive);
+            verify_for_range(3, 0, 1, inclusive);
+            verify_for_range(5, 2, -2, inclusive);
+            verify_for_range(i8::MIN, i8::MAX, 1, inclusive);
+            verify_for_range(i8::MAX, i8::MIN, -3, inclusive);
+            verify_for_range(i64::MIN, i64::MAX, i64::MAX / 3, inclusive);
+            verify_for_range(0.0, 5.0, 2.0, inclusive);
+            verify_for_range(1.0, -1.0, -0.5, inclusive);
+            verify_for_range(0.0, 10.0, 0.1, inclusive);
+            verify_for_range(10.0, 0.0, -0.1, inclusive);
+        }
+
+        let mut values = Vec::new();
+        for_range!(v in u8: 0 ..= 255, step 51 => { values.push(v); });
+        assert_eq!(values, [0, 51, 102, 153, 204, 255]);
+        values.clear();
+        for_range!(v in u8: 255 downto 0 by 85 => { values.push(v); });
+        assert_eq!(values, [255, 170, 85, 0]);
+    }
+
+    #[test]
+    fn for_range_break_continue() {
+        let mut values = Vec::new();
+        for_range!(v in 0 .. 10 => {
+            if v % 2 == 0 {
+                continue;
+            }
+            if v > 6 {
+                break;
+            }
+            values.push(v);
+        });
+        assert_eq!(values, [1, 3, 5]);
+
+        let mut pairs = Vec::new();
+        for_range!('outer: i in 0 ..= 3 => {
+            for_range!(j in 0 ..= 3 => {
+                if j > i {
+                    continue 'outer;
+                }
+                if i == 3 {
+                    break 'outer;
+                }
+                pairs.push((i, j));
+            });
+        });
+        assert_eq!(pairs, [(0, 0), (1, 0), (1, 1), (2, 0), (2, 1), (2, 2)]);
+    }
+
+    static PORTS: BasicRange<u16> = BasicRange::<u16>::const_new(8000, 8010, 2, true);
+    const PORT_COUNT: usize = PORTS.const_len();
+    const COUNTDOWN: BasicRange<u8> = BasicRange::<u8>::const_new(255, 0, -50, false);
+
+    #[test]
+    fn const_new() {
+        assert_eq!(PORT_COUNT, 6);
+        verify_range(vec![8000, 8002, 8004, 8006, 8008, 8010], PORTS);
+        verify_range(vec![255, 205, 155, 105, 55, 5], COUNTDOWN);
+        assert_eq!(COUNTDOWN.const_value_at(5), Some(5));
+        assert_eq!(COUNTDOWN.const_value_at(6), None);
+        assert!(COUNTDOWN.const_contains(105));
+        assert!(!COUNTDOWN.const_contains(100));
+        assert!(!COUNTDOWN.const_contains(0));
+    }
+
+    macro_rules! verify_const_new {
+        ($t:ty, $start:expr, $end:expr, $step:expr) => {
+            for inclusive in [false, true] {
+                let r = BasicRange::<$t>::new($start, $end, $step, inclusive);
+                let c = BasicRange::<$t>::const_new($start, $end, $step, inclusive);
+                assert_eq!(c.const_len(), r.len());
+                let values: Vec<$t> = r.into_iter().collect();
+                assert_eq!(c.into_iter().collect::<Vec<$t>>(), values);
+                for (i, v) in values.iter().enumerate() {
+                    assert_eq!(c.const_value_at(i), Some(*v));
+                    assert_eq!(r.value_at(i), Some(*v));
+                    assert!(c.const_contains(*v));
+                    assert!(r.contains(*v));
+                }
+                assert_eq!(c.const_value_at(values.len()), None);
+                assert_eq!(r.value_at(values.len()), None);
+            }
+        };
+    }
+
+    #[test]
+    fn const_new_same_as_new() {
+        verify_const_new!(u8, 0, 255, 1);
+        verify_const_new!(u8, 0, 255, 5);
+        verify_const_new!(u8, 255, 0, -3);
+        verify_const_new!(u8, 3, 200, i8::MAX);
+        verify_const_new!(i8, -128, 127, 1);
+        verify_const_new!(i8, 127, -128, -7);
+        verify_const_new!(i8, 5, 5, 2);
+        verify_const_new!(u16, 0, 1000, 7);
+        verify_const_new!(i16, -1000, 1000, 33);
+        verify_const_new!(u32, 10, 0, -4);
+        verify_const_new!(i32, 0, 5, 2);
+        verify_const_new!(i32, 3, 0, 1);
+        verify_const_new!(u64, 0, 100, 10);
+        verify_const_new!(i64, i64::MIN, i64::MAX, i64::MAX);
+        verify_const_new!(u128, 0, 50, 7);
+        verify_const_new!(i128, -50, 50, 9);
+        verify_const_new!(usize, 1, 9, 3);
+        verify_const_new!(isize, 9, -9, -4);
+    }
+
+    #[test]
+    fn contains() {
+        let r = BasicRange::new(0, 10, 3, true);
+        assert!(r.contains(0) && r.contains(9));
+        assert!(!r.contains(10) && !r.contains(-3) && !r.contains(4));
+        let r = BasicRange::new(5.0, 2.0, -1.5, true);
+        assert!(r.contains(3.5) && !r.contains(3.0));
+        assert!(!BasicRange::new(0, 0, 1, false).contains(0));
+    }
+
+    #[test]
+    fn step_conversion_keeps_bits() {
+        assert_eq!(200u8.to_step(), -56i8);
+        assert_eq!(u8::from_step(-56), 200);
+        assert_eq!(u16::MAX.to_step(), -1i16);
+        assert_eq!(u32::from_step(i32::MIN), 1 << 31);
+        assert_eq!(u64::MAX.to_step(), -1i64);
+        assert_eq!(u128::MAX.to_step(), -1i128);
+        assert_eq!(
+            u128::MAX.to_extended_step(),
+            ExtendedI128::from_u128(u128::MAX)
+        );
+        assert_eq!(u128::from_extended_step(i128::MIN.into()), 1 << 127);
+        assert_eq!(i128::from_extended_step(u128::MAX.into()), -1);
+        assert_eq!(usize::MAX.to_step(), -1isize);
+        assert_eq!(usize::from_extended_step(-1), usize::MAX);
+        for v in [0u8, 1, 127, 128, 255] {
+            assert_eq!(u8::from_step(v.to_step()), v);
+            assert_eq!(u8::from_extended_step(v.to_extended_step()), v);
+        }
+        for v in [0usize, 1, usize::MAX / 2, usize::MAX / 2 + 1, usize::MAX] {
+            assert_eq!(usize::from_step(v.to_step()), v);
+            assert_eq!(usize::from_extended_step(v.to_extended_step()), v);
+        }
+        for v in [0u128, 1, u128::MAX / 2, u128::MAX / 2 + 1, u128::MAX] {
+            assert_eq!(u128::from_step(v.to_step()), v);
+            assert_eq!(u128::from_extended_step(v.to_extended_step()), v);
+        }
+    }
+
+    #[test]
+    fn extended_i128() {
+        let max = ExtendedI128::from_u128(u128::MAX);
+        let min = ExtendedI128::from_i128(i128::MIN);
+        assert_eq!((max + ExtendedI128::one()).to_u128(), None);
+        assert_eq!((max + ExtendedI128::one()).low_bits(), 0);
+        assert_eq!(max - max, ExtendedI128::zero());
+        assert!(min < ExtendedI128::zero() && ExtendedI128::zero() < max);
+        assert_eq!(
+            (max - min).to_string(),
+            "510423550381407695195061911147652317183"
+        );
+        assert_eq!(min.to_string(), i128::MIN.to_string());
+        assert_eq!(format!("{:>5}", ExtendedI128::from_i128(-7)), "   -7");
+        let seven = ExtendedI128::from_i128(7);
+        let minus_two = ExtendedI128::from_i128(-2);
+        assert_eq!(seven / minus_two, ExtendedI128::from_i128(-3));
+        assert_eq!(-seven % minus_two, ExtendedI128::from_i128(-1));
+        assert_eq!(max * minus_two / minus_two, max);
+        assert_eq!((max - min) / max, ExtendedI128::from_i128(1));
+        assert_eq!(
+            ExtendedI128::from_str_radix("-ff", 16),
+            Ok(-ExtendedI128::from_u128(255))
+        );
+    }
+
+    #[test]
+    fn wide_int_range() {
+        verify_range(
+            vec![u128::MAX - 2, u128::MAX - 1, u128::MAX],
+            range_inclusive!(u128, u128::MAX - 2, u128::MAX),
+        );
+        let r = BasicRange::new(0u128, u128::MAX, i128::MAX, true);
+        assert_eq!(r.len(), 3);
+        assert!(r.contains(i128::MAX as u128) && !r.contains(u128::MAX));
+        verify_range(vec![0, i128::MAX as u128, u128::MAX - 1], r);
+        verify_range(
+            vec![u128::MAX, 1 << 127, 1],
+            BasicRange::new(u128::MAX, 0, i128::MIN + 1, false),
+        );
+        let r = BasicRange::new(i128::MIN, i128::MAX, i128::MAX, true);
+        assert_eq!(r.value_at(2), Some(i128::MAX - 1));
+        verify_range(vec![i128::MIN, -1, i128::MAX - 1], r);
+        verify_range(
+            vec![i128::MAX, 0, i128::MIN + 1],
+            BasicRange::new(i128::MAX, i128::MIN, i128::MIN + 1, true),
+        );
+        verify_range(
+            vec![0, isize::MAX as usize, usize::MAX - 1],
+            BasicRange::new(0usize, usize::MAX, isize::MAX, true),
+        );
+        verify_range(
+            vec![isize::MAX, 0, isize::MIN + 1],
+            BasicRange::new(isize::MAX, isize::MIN, isize::MIN + 1, true),
+        );
+    }
+
+    #[test]
+    fn full_width_len() {
+        let r = BasicRange::<u64>::new(0, u64::MAX, 1, true);
+        assert_eq!(r.checked_len(), None);
+        assert_eq!(r.len_extended(), 1 << 64);
+        assert!(!r.is_empty());
+        assert_eq!((r.first(), r.last()), (Some(0), Some(u64::MAX)));
+        assert!(r.contains(u64::MAX) && r.contains(1 << 40));
+        assert_eq!(r.value_at(usize::MAX), Some(usize::MAX as u64));
+        assert_eq!(r.value_at_extended(u64::MAX as i128), Some(u64::MAX));
+        assert_eq!(r.value_at_extended(1 << 64), None);
+        let r = BasicRange::<i64>::new(i64::MAX, i64::MIN, -1, true);
+        assert_eq!((r.len_extended(), r.last()), (1 << 64, Some(i64::MIN)));
+        let r = BasicRange::<i64>::new(i64::MIN, i64::MAX, 2, false);
+        assert_eq!(r.checked_len(), Some(1 << 63));
+
+        let r = BasicRange::<u128>::new(0, u128::MAX, 1, true);
+        let len = ExtendedI128::from_u128(u128::MAX) + ExtendedI128::one();
+        assert_eq!((r.len_extended(), r.checked_len()), (len, None));
+        assert_eq!(r.last(), Some(u128::MAX));
+        assert!(r.contains(u128::MAX - 1));
+        let index = ExtendedI128::from_u128(u128::MAX);
+        assert_eq!(r.value_at_extended(index), Some(u128::MAX));
+        assert_eq!(r.value_at_extended(len), None);
+        let r = BasicRange::<i128>::new(i128::MIN, i128::MAX, 3, false);
+        assert_eq!(r.len_extended(), ExtendedI128::from_u128(u128::MAX / 3));
+        assert_eq!(r.last(), Some(i128::MAX - 3));
+
+        // for_range! counts the values after the current one, which fits usize
+        let mut values = Vec::new();
+        for_range!(v in u64: 0 ..= u64::MAX => {
+            if v == 3 {
+                break;
+            }
+            values.push(v);
+        });
+        assert_eq!(values, [0, 1, 2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "len beyond usize")]
+    fn len_beyond_usize() {
+        BasicRange::<u128>::new(0, u128::MAX, 1, true).len();
+    }
+
+    #[test]
+    fn magnitude_step() {
+        let r = BasicRange::<u8>::with_magnitude(0, 255, 200, true, true);
+        assert_eq!(r.len(), 2);
+        assert!(r.contains(200) && !r.contains(144));
+        assert_eq!(r.const_len(), 2);
+        assert_eq!(r.const_value_at(1), Some(200));
+        verify_range(vec![0, 200], r);
+        verify_range(
+            vec![255, 55],
+            BasicRange::<u8>::with_magnitude(255, 0, 200, false, true),
+        );
+        verify_range(
+            vec![0, 255],
+            BasicRange::<u8>::with_magnitude(0, 255, 255, true, true),
+        );
+        verify_range(
+            vec![127, -128],
+            BasicRange::<i8>::with_magnitude(127, -128, 255, false, true),
+        );
+        verify_range(
+            vec![-128],
+            BasicRange::<i8>::with_magnitude(-128, 127, 255, true, false),
+        );
+        let r = BasicRange::<u128>::with_magnitude(
+            u128::MAX,
+            0,
+            ExtendedI128::from_u128(u128::MAX),
+            false,
+            true,
+        );
+        assert!(r.const_contains(0) && r.const_len() == 2);
+        verify_range(vec![u128::MAX, 0], r);
+        // Same as new() for steps that fit
+        verify_range(
+            vec![10, 7, 4],
+            BasicRange::<u8>::with_magnitude(10, 2, 3, false, true),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond type width")]
+    fn magnitude_step_beyond_width() {
+        BasicRange::<u8>::with_magnitude(0, 255, 256, true, true);
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    struct Millis(i64);
+
+    impl RangeStep for Millis {
+        type Position = i64;
+        fn to_position(self) -> i64 {
+            self.0
+        }
+        fn from_position(position: i64) -> Self {
+            Millis(position)
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    enum Level {
+        Trace,
+        Debug,
+        Info,
+        Warn,
+        Error,
+    }
+
+    impl RangeStep for Level {
+        type Position = u8;
+        fn to_position(self) -> u8 {
+            self as u8
+        }
+        fn from_position(position: u8) -> Self {
+            [
+                Level::Trace,
+                Level::Debug,
+                Level::Info,
+                Level::Warn,
+                Level::Error,
+            ][position as usize]
+        }
+        fn first() -> Self {
+            Level::Trace
+        }
+        fn last() -> Self {
+            Level::Error
+        }
+    }
+
+    #[test]
+    fn range_step_newtype() {
+        verify_range(
+            vec![Millis(0), Millis(250), Millis(500)],
+            range_exclusive!(Millis, Millis(0), Millis(750), 250),
+        );
+        verify_range(
+            vec![Millis(1000), Millis(400)],
+            rangex_inclusive!(Millis(1000), Millis(0), -600),
+        );
+        verify_range(
+            vec![Millis(i64::MAX), Millis(i64::MAX - 1)],
+            rangex!(Millis(i64::MAX) downto Millis(i64::MAX - 1)),
+        );
+        let r = BasicRange::new(Millis(-10), Millis(10), 5, true);
+        assert_eq!(r.len(), 5);
+        assert!(r.contains(Millis(5)) && !r.contains(Millis(6)));
+        assert_eq!(Millis(3).wrapping_next(-5), Millis(-2));
+    }
+
+    #[test]
+    fn range_step_enum() {
+        use Level::*;
+        verify_range(
+            vec![Trace, Info, Error],
+            range_inclusive!(Level, Trace, Error, 2),
+        );
+        verify_range(vec![Error, Warn], range_exclusive!(Level, Error, Info, -1));
+        verify_range(vec![Debug, Warn], rangex!(Debug ..= Error, step 2));
+        let r = BasicRange::new(Trace, Error, 1, true);
+        assert_eq!(r.len(), 5);
+        assert_eq!(r.value_at(3), Some(Warn));
+        assert_eq!(Error.wrapping_next(1), Trace);
+        assert_eq!(Trace.wrapping_next(-1), Error);
+        // Steps wider than all variants wrap around more than once
+        assert_eq!(Error.wrapping_next(11), Trace);
+        assert_eq!(Trace.wrapping_next(-12), Warn);
+        verify_range(vec![Trace], range_inclusive!(Level, Trace, Error, 15));
+        verify_range(vec![Error], range_inclusive!(Level, Error, Trace, -20));
+    }
+
+    #[test]
+    fn char_range() {
+        verify_range(vec!['a', 'c', 'e'], range_inclusive!(char, 'a', 'e', 2));
+        verify_range(vec!['a', 'c'], range_exclusive!(char, 'a', 'e', 2));
+        verify_range(vec!['z', 'w', 't'], rangex!('z' downto 'r' by 3));
+
+        // Surrogate gap is skipped, not counted
+        let r = BasicRange::new('\u{D7FE}', '\u{E001}', 1, true);
+        assert_eq!(r.len(), 4);
+        verify_range(vec!['\u{D7FE}', '\u{D7FF}', '\u{E000}', '\u{E001}'], r);
+        verify_range(
+            vec!['\u{D7FF}', '\u{E001}'],
+            BasicRange::new('\u{D7FF}', '\u{E002}', 2, false),
+        );
+        verify_range(
+            vec!['\u{E000}', '\u{D7FF}', '\u{D7FE}'],
+            BasicRange::new('\u{E000}', '\u{D7FE}', -1, true),
+        );
+        assert!(BasicRange::new('\u{D000}', '\u{F000}', 1, true).contains('\u{D7FE}'));
+        assert_eq!(
+            BasicRange::new('\u{D000}', '\u{F000}', 1, true).len(),
+            0x1801
+        );
+        assert_eq!(BasicRange::new('\0', char::MAX, 1, true).len(), 0x10F800);
+
+        // Ends at char::MAX, through and backward
+        verify_range(
+            vec!['\u{10FFFD}', char::MAX],
+            BasicRange::new('\u{10FFFD}', char::MAX, 2, true),
+        );
+        verify_range(
+            vec![char::MAX, '\u{10FFFE}'],
+            BasicRange::new(char::MAX, '\u{10FFFD}', -1, false),
+        );
+        verify_range(
+            vec!['\u{1}', '\0'],
+            BasicRange::new('\u{1}', '\0', -1, true),
+        );
+
+        let indexed: Vec<(usize, char)> =
+            crate::indexed_range::IndexedRange::new('x', 'z', 1, true)
+                .into_iter()
+                .collect();
+        assert_eq!(indexed, [(0, 'x'), (1, 'y'), (2, 'z')]);
+    }
+
     #[test]
     fn std_notation() {
         let mut s = 0;
@@ -1114,7 +2886,7 @@ mod main_test {
             assert_eq!(*element, elements[index]);
             s += *element;
         }
-        assert_eq!(s, elements.iter().sum());
+        assert_eq!(s, elements.iter().sum::<i32>());
 
         let mut s = 0.0;
         let elements = vec![10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0, 90.0, 100.0];
@@ -1124,7 +2896,7 @@ mod main_test {
             assert_eq!(*element, elements[index]);
             s += *element;
         }
-        assert_eq!(s, elements.iter().sum());
+        assert_eq!(s, elements.iter().sum::<f64>());
     }
 
     mod success {
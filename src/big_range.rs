@@ -0,0 +1,322 @@
+use crate::ratio_range::{decimal_ratio, ParseDecimalError};
+use num::bigint::{BigInt, BigUint};
+use num::rational::BigRational;
+use num::{Integer, Num, Signed, ToPrimitive, Zero};
+
+/// Big numbers for BigRange, values aren't Copy so they are stepped as the exact type,
+/// BigInt for big integers
+pub trait BigRangeOps: Clone {
+    /// Type of step, and of values while stepping
+    type Exact: Num + Signed + Clone + core::fmt::Debug;
+    fn to_exact(&self) -> Self::Exact;
+    /// Only called with values between start and end
+    fn from_exact(value: Self::Exact) -> Self;
+    fn from_index(index: BigUint) -> Self::Exact;
+    /// Whole *step*s in *distance*, both positive, and whether distance is on step
+    fn steps(distance: &Self::Exact, step: &Self::Exact) -> (BigUint, bool);
+}
+
+impl BigRangeOps for BigInt {
+    type Exact = BigInt;
+    fn to_exact(&self) -> BigInt {
+        self.clone()
+    }
+    fn from_exact(value: BigInt) -> Self {
+        value
+    }
+    fn from_index(index: BigUint) -> BigInt {
+        BigInt::from(index)
+    }
+    fn steps(distance: &BigInt, step: &BigInt) -> (BigUint, bool) {
+        let (steps, rest) = distance.magnitude().div_rem(step.magnitude());
+        (steps, rest.is_zero())
+    }
+}
+
+impl BigRangeOps for BigUint {
+    type Exact = BigInt;
+    fn to_exact(&self) -> BigInt {
+        BigInt::from(self.clone())
+    }
+    fn from_exact(value: BigInt) -> Self {
+        value.to_biguint().expect("BigUint can't be negative")
+    }
+    fn from_index(index: BigUint) -> BigInt {
+        BigInt::from(index)
+    }
+    fn steps(distance: &BigInt, step: &BigInt) -> (BigUint, bool) {
+        BigInt::steps(distance, step)
+    }
+}
+
+/// Exact rationals, steps like 1/3 or 0.1 don't drift
+impl BigRangeOps for BigRational {
+    type Exact = BigRational;
+    fn to_exact(&self) -> BigRational {
+        self.clone()
+    }
+    fn from_exact(value: BigRational) -> Self {
+        value
+    }
+    fn from_index(index: BigUint) -> BigRational {
+        BigRational::from_integer(BigInt::from(index))
+    }
+    fn steps(distance: &BigRational, step: &BigRational) -> (BigUint, bool) {
+        let steps = distance / step;
+        let whole = steps.to_integer().to_biguint().unwrap();
+        (whole, steps.is_integer())
+    }
+}
+
+impl BigRange<BigRational> {
+    /// Range of exact decimals of any length, e.g. `from_decimals("0", "1", "0.1", true)` has 11 values
+    pub fn from_decimals(
+        start: &str,
+        end: &str,
+        step: &str,
+        inclusive: bool,
+    ) -> Result<Self, ParseDecimalError> {
+        let step = decimal_ratio::<BigInt>(step)?;
+        if step.is_zero() {
+            return Err(ParseDecimalError::ZeroStep);
+        }
+        Ok(BigRange::new(
+            decimal_ratio(start)?,
+            decimal_ratio(end)?,
+            step,
+            inclusive,
+        ))
+    }
+}
+
+/// Range of BigInt, BigUint or BigRational, same semantics as BasicRange,
+/// len() and value_at() are big integers, e.g. 2^200 to 2^200 + 10^6 by 7
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigRange<T>
+where
+    T: BigRangeOps,
+{
+    pub start: T,
+    pub step: T::Exact,
+    len: BigUint,
+}
+
+impl<T> BigRange<T>
+where
+    T: BigRangeOps,
+{
+    pub fn new(start: T, end: T, step: impl Into<T::Exact>, inclusive: bool) -> Self {
+        let step = step.into();
+        if step.is_zero() {
+            panic!("Step can't be 0");
+        }
+        let distance = end.to_exact() - start.to_exact();
+        let len = if distance.is_zero() {
+            BigUint::from(inclusive as u8)
+        } else if distance.is_negative() != step.is_negative() {
+            BigUint::zero()
+        } else {
+            let (steps, on_step) = T::steps(&distance.abs(), &step.abs());
+            steps + BigUint::from((inclusive || !on_step) as u8)
+        };
+        BigRange { start, step, len }
+    }
+
+    /// Number of values the range yields
+    pub fn len(&self) -> BigUint {
+        self.len.clone()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len.is_zero()
+    }
+
+    /// Value at *index*, the nth value, None if index is not below len()
+    pub fn value_at(&self, index: &BigUint) -> Option<T> {
+        if *index < self.len {
+            let offset = T::from_index(index.clone()) * self.step.clone();
+            Some(T::from_exact(self.start.to_exact() + offset))
+        } else {
+            None
+        }
+    }
+
+    /// Whether the range yields *value*
+    pub fn contains(&self, value: &T) -> bool {
+        let offset = value.to_exact() - self.start.to_exact();
+        if !offset.is_zero() && offset.is_negative() != self.step.is_negative() {
+            return false;
+        }
+        let (steps, on_step) = T::steps(&offset.abs(), &self.step.abs());
+        on_step && steps < self.len
+    }
+}
+
+impl<T> IntoIterator for BigRange<T>
+where
+    T: BigRangeOps,
+{
+    type Item = T;
+    type IntoIter = BigRangeIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BigRangeIter {
+            current: self.start.to_exact(),
+            step: self.step,
+            remaining: self.len,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BigRangeIter<T>
+where
+    T: BigRangeOps,
+{
+    current: T::Exact,
+    step: T::Exact,
+    remaining: BigUint,
+}
+
+impl<T> Iterator for BigRangeIter<T>
+where
+    T: BigRangeOps,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.remaining.is_zero() {
+            return None;
+        }
+        self.remaining -= 1u8;
+        if self.remaining.is_zero() {
+            return Some(T::from_exact(self.current.clone()));
+        }
+        let next = self.current.clone() + self.step.clone();
+        Some(T::from_exact(core::mem::replace(&mut self.current, next)))
+    }
+
+    /// Skips n values with one multiplication
+    fn nth(&mut self, n: usize) -> Option<T> {
+        let skip = BigUint::from(n);
+        if skip >= self.remaining {
+            self.remaining.set_zero();
+            return None;
+        }
+        self.remaining -= &skip;
+        self.current = self.current.clone() + T::from_index(skip) * self.step.clone();
+        self.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.remaining.to_usize() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+    use num::traits::Pow;
+
+    fn big(value: i64) -> BigInt {
+        BigInt::from(value)
+    }
+
+    #[test]
+    fn same_as_basic_range() {
+        use crate::basic_range::BasicRange;
+        for (start, end, step) in [
+            (0, 10, 3),
+            (0, 9, 3),
+            (10, 0, -3),
+            (9, 0, -3),
+            (0, 0, 1),
+            (3, 0, 1),
+            (0, 3, -1),
+        ] {
+            for inclusive in [false, true] {
+                let expect: Vec<BigInt> = BasicRange::new(start, end, step, inclusive)
+                    .into_iter()
+                    .map(BigInt::from)
+                    .collect();
+                let r = BigRange::new(big(start), big(end), step, inclusive);
+                assert_eq!(r.len(), BigUint::from(expect.len()));
+                assert_eq!(r.into_iter().collect::<Vec<_>>(), expect);
+            }
+        }
+    }
+
+    #[test]
+    fn big_rational() {
+        let third = BigRational::new(big(1), big(3));
+        let r = BigRange::new(
+            BigRational::zero(),
+            BigRational::from_integer(big(2)),
+            third.clone(),
+            false,
+        );
+        assert_eq!(r.len(), BigUint::from(6u8));
+        assert!(r.contains(&BigRational::new(big(5), big(3))));
+        assert!(!r.contains(&BigRational::from_integer(big(2))));
+        assert_eq!(r.into_iter().nth(4), Some(BigRational::new(big(4), big(3))));
+
+        let r = BigRange::<BigRational>::from_decimals("1", "0", "-0.3", true).unwrap();
+        assert_eq!(r.len(), BigUint::from(4u8));
+        assert_eq!(
+            r.into_iter().last(),
+            Some(BigRational::new(big(1), big(10)))
+        );
+        let tiny = "0.000000000000000000000000000001";
+        let r = BigRange::<BigRational>::from_decimals(
+            "0",
+            "0.00000000000000000000000000001",
+            tiny,
+            true,
+        )
+        .unwrap();
+        assert_eq!(r.len(), BigUint::from(11u8));
+        assert!(BigRange::<BigRational>::from_decimals("0", "1", "1/3", true).is_err());
+        assert_eq!(
+            BigRange::<BigRational>::from_decimals("0", "1", "-0.000", true),
+            Err(ParseDecimalError::ZeroStep)
+        );
+    }
+
+    #[test]
+    // Steps through 142858 big integers, hours under Miri
+    #[cfg_attr(miri, ignore)]
+    fn big_values() {
+        let start = BigUint::from(2u8).pow(200u32);
+        let end = &start + BigUint::from(10u32).pow(6u32);
+        let r = BigRange::new(start.clone(), end.clone(), 7, true);
+        assert_eq!(r.len(), BigUint::from(142858u32));
+        assert!(r.contains(&(&start + 7u8)));
+        assert!(!r.contains(&(&start + 8u8)) && !r.contains(&(&end + 6u8)));
+        assert_eq!(r.value_at(&BigUint::from(2u8)), Some(&start + 14u8));
+        assert_eq!(r.value_at(&r.len()), None);
+        let mut values = r.clone().into_iter();
+        assert_eq!(values.nth(142857), Some(&start + 999999u32));
+        assert_eq!(values.next(), None);
+        assert_eq!(r.into_iter().last(), Some(&start + 999999u32));
+
+        // Counting down to 0 for BigUint
+        let values: Vec<_> = BigRange::new(BigUint::from(7u8), BigUint::zero(), -3, true)
+            .into_iter()
+            .collect();
+        assert_eq!(values, [7u8, 4, 1].map(BigUint::from));
+
+        // len beyond usize
+        let huge = BigInt::from(2).pow(100u32);
+        let r = BigRange::new(-&huge, huge.clone(), 1, false);
+        assert_eq!(r.len(), BigUint::from(2u8).pow(101u32));
+        assert_eq!(r.into_iter().size_hint(), (usize::MAX, None));
+        let r = BigRange::new(huge.clone(), -&huge, -(&huge), true);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            [huge.clone(), big(0), -huge]
+        );
+    }
+}
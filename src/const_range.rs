@@ -0,0 +1,169 @@
+use crate::basic_range::*;
+
+/// Calls for_each() spells out per loop turn
+const UNROLL: usize = 8;
+
+/// One call of *f* per offset below *count*, for the values from *index* on
+macro_rules! unrolled_calls {
+    ($f:ident, $range:ty, $index:expr, $count:expr; $($offset:literal)*) => {
+        $(
+            if $offset < $count {
+                $f(<$range>::value_at($index + $offset));
+            }
+        )*
+    };
+}
+
+/// Range with start, end, step and inclusiveness fixed at compile time
+///
+/// Yields the same values as `BasicRange::<i64>::new(START, END, STEP, INCLUSIVE)`, the number of
+/// values is the associated const `LEN`, and a step of 0 is a compile error once the range is used.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConstRange<const START: i64, const END: i64, const STEP: i64, const INCLUSIVE: bool>;
+
+impl<const START: i64, const END: i64, const STEP: i64, const INCLUSIVE: bool>
+    ConstRange<START, END, STEP, INCLUSIVE>
+{
+    /// Number of values, same rule as BasicRange::new for end not on step,
+    /// a compile error if it's beyond usize, e.g. all i64 values
+    /// ```compile_fail
+    /// let _ = rangex::const_range::ConstRange::<{ i64::MIN }, { i64::MAX }, 1, true>::LEN;
+    /// ```
+    pub const LEN: usize = {
+        assert!(STEP != 0, "Step can't be 0");
+        if (START < END && STEP < 0) || (START > END && STEP > 0) {
+            0
+        } else {
+            let distance = (END as i128 - START as i128).unsigned_abs();
+            let step = (STEP as i128).unsigned_abs();
+            let on_step = distance.is_multiple_of(step);
+            let len = distance / step + if INCLUSIVE || !on_step { 1 } else { 0 };
+            assert!(len <= usize::MAX as u128, "ConstRange::LEN beyond usize");
+            len as usize
+        }
+    };
+
+    pub const fn new() -> Self {
+        ConstRange
+    }
+
+    pub const fn len(&self) -> usize {
+        Self::LEN
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        0 == Self::LEN
+    }
+
+    /// Value at *index*, index is not checked against LEN
+    pub const fn value_at(index: usize) -> i64 {
+        (START as i128 + index as i128 * STEP as i128) as i64
+    }
+
+    /// All values as an array, N must be LEN, e.g.
+    /// `const V: [i64; 3] = ConstRange::<0, 5, 2, false>::to_array();`
+    pub const fn to_array<const N: usize>() -> [i64; N] {
+        assert!(N == Self::LEN, "Array length must be ConstRange::LEN");
+        let mut values = [0; N];
+        let mut index = 0;
+        while index < N {
+            values[index] = Self::value_at(index);
+            index += 1;
+        }
+        values
+    }
+
+    /// Call *f* for every value, unrolled by hand in blocks of UNROLL calls, the rest behind
+    /// constant conditions, so ranges of at most UNROLL values are straight-line code
+    #[inline(always)]
+    pub fn for_each<F: FnMut(i64)>(mut f: F) {
+        let mut index = 0;
+        while Self::LEN - index >= UNROLL {
+            unrolled_calls!(f, Self, index, UNROLL; 0 1 2 3 4 5 6 7);
+            index += UNROLL;
+        }
+        unrolled_calls!(f, Self, index, Self::LEN - index; 0 1 2 3 4 5 6);
+    }
+
+    /// Same range as BasicRange
+    pub fn to_basic_range(&self) -> BasicRange<i64> {
+        let _ = Self::LEN;
+        BasicRange::new(START, END, STEP, INCLUSIVE)
+    }
+}
+
+impl<const START: i64, const END: i64, const STEP: i64, const INCLUSIVE: bool> IntoIterator
+    for ConstRange<START, END, STEP, INCLUSIVE>
+{
+    type Item = i64;
+    type IntoIter = BasicRangeIter<i64>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.to_basic_range().into_iter()
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    macro_rules! verify_const_range {
+        ($start:literal, $end:literal, $step:literal, $inclusive:literal) => {{
+            type R = ConstRange<$start, $end, $step, $inclusive>;
+            let expect: Vec<i64> = BasicRange::new($start, $end, $step, $inclusive)
+                .into_iter()
+                .collect();
+            assert_eq!(R::LEN, expect.len());
+            assert_eq!(R::new().into_iter().collect::<Vec<_>>(), expect);
+            let mut values = Vec::new();
+            R::for_each(|v| values.push(v));
+            assert_eq!(values, expect);
+        }};
+    }
+
+    #[test]
+    fn same_as_basic_range() {
+        verify_const_range!(0, 3, 1, false);
+        verify_const_range!(0, 2, 1, true);
+        verify_const_range!(3, 0, -1, false);
+        verify_const_range!(3, 1, -1, true);
+        verify_const_range!(0, 5, 2, false);
+        verify_const_range!(0, 5, 2, true);
+        verify_const_range!(0, 4, 2, false);
+        verify_const_range!(0, 4, 2, true);
+        verify_const_range!(5, 2, -2, false);
+        verify_const_range!(5, 2, -2, true);
+        verify_const_range!(0, 0, 2, false);
+        verify_const_range!(0, 0, -2, true);
+        verify_const_range!(3, 0, 1, true);
+        verify_const_range!(0, 1, -1, false);
+        // Whole blocks of for_each() and the rest
+        verify_const_range!(0, 8, 1, false);
+        verify_const_range!(0, 8, 1, true);
+        verify_const_range!(0, 16, 1, false);
+        verify_const_range!(100, -3, -7, true);
+        verify_const_range!(-50, 50, 3, false);
+    }
+
+    #[test]
+    fn to_array() {
+        const VALUES: [i64; 3] = ConstRange::<0, 5, 2, false>::to_array();
+        assert_eq!(VALUES, [0, 2, 4]);
+        const EMPTY: [i64; 0] = ConstRange::<3, 0, 1, true>::to_array();
+        assert_eq!(EMPTY, [0i64; 0]);
+        assert_eq!(ConstRange::<5, 2, -2, true>::to_array::<2>(), [5, 3]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn full_width_len() {
+        assert_eq!(
+            ConstRange::<{ i64::MIN }, { i64::MAX }, 1, false>::LEN,
+            usize::MAX
+        );
+        assert_eq!(
+            ConstRange::<{ i64::MAX }, { i64::MIN }, -2, true>::LEN,
+            1 << 63
+        );
+    }
+}
@@ -0,0 +1,349 @@
+use crate::basic_range::*;
+use core::fmt;
+use num::{FromPrimitive, ToPrimitive, Zero};
+
+/// What from_mixed() does with an endpoint outside the range's type
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EndpointPolicy {
+    /// Return OutOfRangeError
+    #[default]
+    Error,
+    /// Use min or max of the range's type instead
+    Clamp,
+}
+
+/// Which part of a range doesn't fit the range's type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutOfRangeError {
+    Start,
+    End,
+    Step,
+}
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OutOfRangeError::Start => "start out of range for type",
+            OutOfRangeError::End => "end out of range for type",
+            OutOfRangeError::Step => "step out of range for type",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for OutOfRangeError {}
+
+impl<T> BasicRange<T>
+where
+    T: IteratorOps,
+{
+    /// Range from endpoints and step of other integer types, e.g. u32 start and usize end
+    /// into BasicRange<u64>, steps wider than T::Step are used by magnitude, steps are never clamped,
+    /// a zero step is OutOfRangeError::Step
+    pub fn from_mixed<S, E, P>(
+        start: S,
+        end: E,
+        step: P,
+        inclusive: bool,
+        policy: EndpointPolicy,
+    ) -> Result<Self, OutOfRangeError>
+    where
+        T: TryFrom<S> + TryFrom<E>,
+        T::Step: TryFrom<P>,
+        T::ExtendedStep: TryFrom<P>,
+        S: Zero + PartialOrd + Copy,
+        E: Zero + PartialOrd + Copy,
+        P: Copy,
+    {
+        let start = convert(start, policy).ok_or(OutOfRangeError::Start)?;
+        let end = convert(end, policy).ok_or(OutOfRangeError::End)?;
+        if let Ok(step) = T::Step::try_from(step) {
+            if step == T::Step::zero() {
+                return Err(OutOfRangeError::Step);
+            }
+            return Ok(Self::new(start, end, step, inclusive));
+        }
+        let step = T::ExtendedStep::try_from(step).map_err(|_| OutOfRangeError::Step)?;
+        let magnitude = step.abs();
+        if magnitude > T::max().to_extended_step() - T::min().to_extended_step() {
+            return Err(OutOfRangeError::Step);
+        }
+        let forward = step > T::ExtendedStep::zero();
+        Ok(Self::with_magnitude(
+            start, end, magnitude, forward, inclusive,
+        ))
+    }
+
+    /// Same values as a range of U, e.g. BasicRange<u64> as BasicRange<u32>,
+    /// error if start, last value or step doesn't fit U
+    pub fn try_cast<U>(&self) -> Result<BasicRange<U>, OutOfRangeError>
+    where
+        T: Zero,
+        U: IteratorOps + TryFrom<T>,
+        U::Step: TryFrom<T::ExtendedStep>,
+        U::ExtendedStep: TryFrom<T::ExtendedStep>,
+    {
+        let cast = |end: T, inclusive: bool| {
+            BasicRange::<U>::from_mixed(
+                self.start,
+                end,
+                self.extended_step,
+                inclusive,
+                EndpointPolicy::Error,
+            )
+        };
+        match self.last() {
+            Some(last) if self.inclusive_or_not_on_step => cast(last, true),
+            // Exclusive on step, end past the last value is kept if it fits
+            Some(last) => cast(self.end, false).or_else(|_| cast(last, true)),
+            None => cast(self.end, false).or_else(|_| cast(self.start, false)),
+        }
+    }
+
+    /// Same progression as floats, e.g. BasicRange<u64> as BasicRange<f64>, values are rounded
+    /// to the nearest float, None if the float range wouldn't have the same len(),
+    /// an exclusive end on step stays exclusive, an end off step becomes inclusive of the last
+    /// value like try_cast(), which new() already stores the same way
+    pub fn cast_lossy<F>(&self) -> Option<BasicRange<F>>
+    where
+        T: ToPrimitive,
+        T::ExtendedStep: ToPrimitive,
+        F: IteratorOps + FromPrimitive,
+        F::Step: FromPrimitive,
+    {
+        let start = F::from_f64(self.start.to_f64()?)?;
+        let step = F::Step::from_f64(self.extended_step.to_f64()?)?;
+        if step == F::Step::zero() {
+            return None;
+        }
+        let last = match self.last() {
+            Some(last) => F::from_f64(last.to_f64()?)?,
+            None => return Some(BasicRange::new(start, start, step, false)),
+        };
+        // Past the float's precision last + step is last again, values would repeat
+        if last.wrapping_next(step) == last {
+            return None;
+        }
+        let same_len = |range: &BasicRange<F>| {
+            range.checked_len().is_some() && range.checked_len() == self.checked_len()
+        };
+        if !self.inclusive_or_not_on_step {
+            // Exclusive on step, end past the last value is kept if it gives the same range
+            let exclusive = self
+                .end
+                .to_f64()
+                .and_then(F::from_f64)
+                .map(|end| BasicRange::new(start, end, step, false))
+                .filter(same_len);
+            if exclusive.is_some() {
+                return exclusive;
+            }
+        }
+        Some(BasicRange::new(start, last, step, true)).filter(same_len)
+    }
+
+    /// Endpoints of the same width reinterpreted bit for bit, e.g. i32 as u32, without checks
+    pub fn from_same_width<S>(start: S, end: S, step: T::Step, inclusive: bool) -> Self
+    where
+        S: SizeCompatible<T>,
+    {
+        Self::new(start.reinterpret(), end.reinterpret(), step, inclusive)
+    }
+}
+
+/// *value* as T, None or clamped by *policy* if it doesn't fit
+fn convert<T, V>(value: V, policy: EndpointPolicy) -> Option<T>
+where
+    T: IteratorOps + TryFrom<V>,
+    V: Zero + PartialOrd + Copy,
+{
+    match (T::try_from(value), policy) {
+        (Ok(value), _) => Some(value),
+        (Err(_), EndpointPolicy::Error) => None,
+        // Every integer type holds 0, so a value that doesn't fit is beyond one bound
+        (Err(_), EndpointPolicy::Clamp) => Some(if value < V::zero() {
+            T::min()
+        } else {
+            T::max()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    fn values<T: IteratorOps>(range: BasicRange<T>) -> Vec<T> {
+        range.into_iter().collect()
+    }
+
+    #[test]
+    fn mixed_endpoints() {
+        let r =
+            BasicRange::<u64>::from_mixed(1u32, 10usize, 3i8, true, EndpointPolicy::Error).unwrap();
+        assert_eq!(values(r), [1, 4, 7, 10]);
+        let r = BasicRange::<i16>::from_mixed(300u64, -2i64, -100i32, false, EndpointPolicy::Error)
+            .unwrap();
+        assert_eq!(values(r), [300, 200, 100, 0]);
+
+        assert_eq!(
+            BasicRange::<u8>::from_mixed(-1i32, 10u32, 1, true, EndpointPolicy::Error).unwrap_err(),
+            OutOfRangeError::Start
+        );
+        assert_eq!(
+            BasicRange::<u8>::from_mixed(0, 256u16, 1, true, EndpointPolicy::Error).unwrap_err(),
+            OutOfRangeError::End
+        );
+        assert_eq!(
+            BasicRange::<u8>::from_mixed(0, 255, 256i32, true, EndpointPolicy::Clamp).unwrap_err(),
+            OutOfRangeError::Step
+        );
+        assert_eq!(
+            BasicRange::<u64>::from_mixed(0u8, 1u8, u128::MAX, true, EndpointPolicy::Clamp)
+                .unwrap_err(),
+            OutOfRangeError::Step
+        );
+        assert_eq!(
+            BasicRange::<u16>::from_mixed(0u8, 10u32, 0i64, true, EndpointPolicy::Error)
+                .unwrap_err(),
+            OutOfRangeError::Step
+        );
+        assert_eq!(
+            BasicRange::<u128>::from_mixed(0u8, 10u8, 0u128, false, EndpointPolicy::Clamp)
+                .unwrap_err(),
+            OutOfRangeError::Step
+        );
+    }
+
+    #[test]
+    fn clamped_endpoints() {
+        let r = BasicRange::<u8>::from_mixed(-5i64, 1000usize, 100i32, true, EndpointPolicy::Clamp)
+            .unwrap();
+        assert_eq!(values(r), [0, 100, 200]);
+        let r = BasicRange::<i8>::from_mixed(u64::MAX, i32::MIN, -127, true, EndpointPolicy::Clamp)
+            .unwrap();
+        assert_eq!(values(r), [127, 0, -127]);
+        assert_eq!(
+            EndpointPolicy::default(),
+            EndpointPolicy::Error,
+            "errors unless asked to clamp"
+        );
+    }
+
+    #[test]
+    fn wide_step() {
+        // 200 doesn't fit i8, the Step of u8, but is within its width
+        let r =
+            BasicRange::<u8>::from_mixed(0u8, 255u8, 200u16, true, EndpointPolicy::Error).unwrap();
+        assert_eq!(values(r), [0, 200]);
+        let r =
+            BasicRange::<u8>::from_mixed(255u8, 0u8, -255i64, true, EndpointPolicy::Error).unwrap();
+        assert_eq!(values(r), [255, 0]);
+        let r =
+            BasicRange::<u128>::from_mixed(0u8, u128::MAX, u128::MAX, true, EndpointPolicy::Error)
+                .unwrap();
+        assert_eq!(values(r), [0, u128::MAX]);
+    }
+
+    #[test]
+    fn cast() {
+        let r = BasicRange::<u64>::new(0, 10, 2, false);
+        let cast = r.try_cast::<u32>().unwrap();
+        assert_eq!((cast.end, cast.inclusive_or_not_on_step), (10, false));
+        assert_eq!(values(cast), [0, 2, 4, 6, 8]);
+        // 256 doesn't fit, the same values through 255 do
+        let cast = BasicRange::<u64>::new(0, 256, 5, false)
+            .try_cast::<u8>()
+            .unwrap();
+        assert_eq!(cast.len(), 52);
+        assert_eq!(cast.into_iter().last(), Some(255));
+        let cast = BasicRange::<i64>::new(10, -10, -7, true)
+            .try_cast::<i8>()
+            .unwrap();
+        assert_eq!(values(cast), [10, 3, -4]);
+        assert_eq!(
+            BasicRange::<i64>::new(10, -10, -7, true)
+                .try_cast::<u8>()
+                .unwrap_err(),
+            OutOfRangeError::End
+        );
+        assert_eq!(
+            BasicRange::<u32>::new(0, 1000, 300, true)
+                .try_cast::<u8>()
+                .unwrap_err(),
+            OutOfRangeError::End
+        );
+        assert_eq!(
+            BasicRange::<u32>::new(0, 100, 1000, true)
+                .try_cast::<i8>()
+                .unwrap_err(),
+            OutOfRangeError::Step
+        );
+        // Step wider than i8 but not than u8
+        let cast = BasicRange::<u16>::new(0, 255, 200, true)
+            .try_cast::<u8>()
+            .unwrap();
+        assert_eq!(values(cast), [0, 200]);
+        let cast = BasicRange::<u128>::new(u128::MAX, 0, -3, true).try_cast::<u8>();
+        assert_eq!(cast.unwrap_err(), OutOfRangeError::Start);
+        let cast = BasicRange::<u128>::new(9, 0, -3, true)
+            .try_cast::<i8>()
+            .unwrap();
+        assert_eq!(values(cast), [9, 6, 3, 0]);
+
+        // Empty ranges stay empty
+        let empty = BasicRange::<u64>::new(1000, 0, 1, true);
+        assert!(empty.try_cast::<u16>().unwrap().is_empty());
+        assert!(empty.try_cast::<u8>().is_err());
+        assert!(BasicRange::<u64>::new(7, 7, 1, false)
+            .try_cast::<u8>()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn cast_lossy() {
+        let r = BasicRange::<u64>::new(0, 1000, 3, true);
+        let floats = r.cast_lossy::<f64>().unwrap();
+        assert_eq!(floats.len(), 334);
+        assert_eq!(floats.value_at(333), Some(999.0));
+        assert_eq!(values(floats).len(), 334);
+        let floats = BasicRange::<i32>::new(5, -5, -2, false)
+            .cast_lossy::<f32>()
+            .unwrap();
+        assert_eq!(values(floats), [5.0, 3.0, 1.0, -1.0, -3.0]);
+        let floats = BasicRange::<f64>::new(0.0, 1.0, 0.25, true)
+            .cast_lossy::<f32>()
+            .unwrap();
+        assert_eq!(values(floats), [0.0, 0.25, 0.5, 0.75, 1.0]);
+        assert!(BasicRange::<u8>::new(0, 0, 1, false)
+            .cast_lossy::<f64>()
+            .unwrap()
+            .is_empty());
+        // Exclusive on step keeps its end, off step is stored like inclusive of the last value
+        let floats = BasicRange::<u64>::new(0, 9, 3, false)
+            .cast_lossy::<f64>()
+            .unwrap();
+        assert_eq!((floats.end, floats.inclusive_or_not_on_step), (9.0, false));
+        assert_eq!(values(floats), [0.0, 3.0, 6.0]);
+        let r = BasicRange::<i64>::new(0, -10, -3, false);
+        let floats = r.cast_lossy::<f64>().unwrap();
+        assert_eq!((floats.end, floats.inclusive_or_not_on_step), (-12.0, true));
+        assert_eq!((r.end, r.inclusive_or_not_on_step), (-12, true));
+        assert_eq!(values(floats), [0.0, -3.0, -6.0, -9.0]);
+        // 2^24 + 1 rounds to 2^24 in f32, one value less
+        let r = BasicRange::<u32>::new(1 << 24, (1 << 24) + 1, 1, true);
+        assert!(r.cast_lossy::<f32>().is_none());
+        assert!(r.cast_lossy::<f64>().is_some());
+    }
+
+    #[test]
+    fn same_width() {
+        let r = BasicRange::<u8>::from_same_width(0i8, -1i8, 85, true);
+        assert_eq!(values(r), [0, 85, 170, 255]);
+        let r = BasicRange::<u32>::from_same_width(-1i32, 0i32, -1, true);
+        assert_eq!(r.len(), 1 + u32::MAX as usize);
+        let r = BasicRange::<isize>::from_same_width(-3isize, 3, 3, false);
+        assert_eq!(values(r), [-3, 0]);
+    }
+}
@@ -0,0 +1,413 @@
+use crate::basic_range::*;
+use chrono::{Datelike, NaiveDate};
+
+/// Dates step by days, weeks are steps of 7, e.g. every other Monday is
+/// `BasicRange::new(monday, end, 14, true)`
+impl RangeStep for NaiveDate {
+    type Position = i32;
+    fn to_position(self) -> i32 {
+        self.num_days_from_ce()
+    }
+    fn from_position(position: i32) -> Self {
+        NaiveDate::from_num_days_from_ce_opt(position).expect("Day beyond NaiveDate::MAX")
+    }
+    fn first() -> Self {
+        NaiveDate::MIN
+    }
+    fn last() -> Self {
+        NaiveDate::MAX
+    }
+}
+
+/// Day of month for months shorter than the start day, e.g. stepping from January 31
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MonthDay {
+    /// Day of start, clamped to the last day of shorter months, Jan 31 -> Feb 28 -> Mar 31
+    #[default]
+    Clamp,
+    /// Last day of every month if start is a month end, Jan 31 -> Feb 28 -> Mar 31,
+    /// Feb 28 -> Mar 31 -> Apr 30, otherwise same as Clamp
+    KeepMonthEnd,
+}
+
+/// Range of dates by months or years, same semantics as BasicRange,
+/// e.g. every month end from 2023-01 through 2026-10:
+/// `MonthRange::new(jan_31_2023, oct_31_2026, 1, true, MonthDay::KeepMonthEnd)`
+#[derive(Clone, Copy, Debug)]
+pub struct MonthRange {
+    /// Months counted from year 0, year * 12 + month0
+    pub months: BasicRange<i32>,
+    pub day: u32,
+    pub month_end: bool,
+}
+
+impl MonthRange {
+    pub fn new(
+        start: NaiveDate,
+        end: NaiveDate,
+        months: i32,
+        inclusive: bool,
+        month_day: MonthDay,
+    ) -> Self {
+        if months == 0 {
+            panic!("Step can't be 0");
+        }
+        let month_end = month_day == MonthDay::KeepMonthEnd
+            && start.day() == last_day(start.year(), start.month0());
+        let mut range = MonthRange {
+            months: BasicRange::new(month_index(start), month_index(start), months, false),
+            day: start.day(),
+            month_end,
+        };
+        if (start < end && months < 0) || (start > end && months > 0) {
+            return range;
+        }
+        // Last month on step not past end's month, one step back if its day is past end
+        let start_index = month_index(start);
+        let mut last = start_index + (month_index(end) - start_index) / months * months;
+        let date = range.date(last);
+        let past_end = if months > 0 { date > end } else { date < end };
+        if past_end || (date == end && !inclusive) {
+            last -= months;
+        }
+        if (months > 0 && last >= start_index) || (months < 0 && last <= start_index) {
+            range.months = BasicRange::new(start_index, last, months, true);
+        }
+        range
+    }
+
+    /// Same as new() by *years*, Feb 29 falls on Feb 28 in common years with Clamp
+    pub fn years(
+        start: NaiveDate,
+        end: NaiveDate,
+        years: i32,
+        inclusive: bool,
+        month_day: MonthDay,
+    ) -> Self {
+        Self::new(start, end, years * 12, inclusive, month_day)
+    }
+
+    fn date(&self, month_index: i32) -> NaiveDate {
+        let (year, month0) = (
+            month_index.div_euclid(12),
+            month_index.rem_euclid(12) as u32,
+        );
+        let last_day = last_day(year, month0);
+        let day = if self.month_end {
+            last_day
+        } else {
+            self.day.min(last_day)
+        };
+        NaiveDate::from_ymd_opt(year, month0 + 1, day).expect("Month beyond NaiveDate::MAX")
+    }
+
+    pub fn len(&self) -> usize {
+        self.months.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.months.is_empty()
+    }
+
+    /// Date at *index*, None if index is not below len()
+    pub fn value_at(&self, index: usize) -> Option<NaiveDate> {
+        self.months.value_at(index).map(|m| self.date(m))
+    }
+}
+
+fn month_index(date: NaiveDate) -> i32 {
+    date.year() * 12 + date.month0() as i32
+}
+
+/// Day before the first of the next month, December of the year of NaiveDate::MAX has no next month
+fn last_day(year: i32, month0: u32) -> u32 {
+    let (next_year, next_month) = if month0 == 11 {
+        (year + 1, 1)
+    } else {
+        (year, month0 + 2)
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first| first.pred_opt())
+        .map_or(31, |last| last.day())
+}
+
+impl IntoIterator for MonthRange {
+    type Item = NaiveDate;
+    type IntoIter = MonthRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        MonthRangeIter {
+            months: self.months.into_iter(),
+            range: self,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MonthRangeIter {
+    range: MonthRange,
+    months: BasicRangeIter<i32>,
+}
+
+impl Iterator for MonthRangeIter {
+    type Item = NaiveDate;
+
+    fn next(&mut self) -> Option<NaiveDate> {
+        self.months.next().map(|m| self.range.date(m))
+    }
+}
+
+/// MonthRange with index, like IndexedRange
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedMonthRange {
+    month_range: MonthRange,
+}
+
+impl IndexedMonthRange {
+    pub fn new(
+        start: NaiveDate,
+        end: NaiveDate,
+        months: i32,
+        inclusive: bool,
+        month_day: MonthDay,
+    ) -> Self {
+        IndexedMonthRange {
+            month_range: MonthRange::new(start, end, months, inclusive, month_day),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct IndexedMonthRangeIter {
+    month_range_iter: MonthRangeIter,
+    index: usize,
+}
+
+impl Iterator for IndexedMonthRangeIter {
+    type Item = (usize, NaiveDate);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let date = self.month_range_iter.next()?;
+        let result = (self.index, date);
+        self.index += 1;
+        Some(result)
+    }
+}
+
+impl IntoIterator for IndexedMonthRange {
+    type Item = (usize, NaiveDate);
+    type IntoIter = IndexedMonthRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IndexedMonthRangeIter {
+            month_range_iter: self.month_range.into_iter(),
+            index: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+    use crate::indexed_range::IndexedRange;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dates(r: impl IntoIterator<Item = NaiveDate>) -> Vec<String> {
+        r.into_iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn day_week_range() {
+        let r = BasicRange::new(ymd(2024, 2, 27), ymd(2024, 3, 2), 1, true);
+        assert_eq!(r.len(), 5);
+        assert_eq!(dates(r)[2], "2024-02-29");
+        // Every other Monday
+        let r = BasicRange::new(ymd(2024, 1, 1), ymd(2024, 2, 26), 14, false);
+        assert_eq!(
+            dates(r),
+            ["2024-01-01", "2024-01-15", "2024-01-29", "2024-02-12"]
+        );
+        let r = BasicRange::new(ymd(2024, 1, 10), ymd(2023, 12, 25), -7, true);
+        assert_eq!(dates(r), ["2024-01-10", "2024-01-03", "2023-12-27"]);
+        let r = BasicRange::new(NaiveDate::MAX.pred_opt().unwrap(), NaiveDate::MAX, 1, true);
+        assert_eq!(r.into_iter().last(), Some(NaiveDate::MAX));
+        let indexed: Vec<_> = IndexedRange::new(ymd(2024, 1, 1), ymd(2024, 1, 2), 1, true)
+            .into_iter()
+            .collect();
+        assert_eq!(indexed, [(0, ymd(2024, 1, 1)), (1, ymd(2024, 1, 2))]);
+    }
+
+    #[test]
+    fn month_range() {
+        let r = MonthRange::new(ymd(2023, 1, 31), ymd(2023, 5, 31), 1, true, MonthDay::Clamp);
+        assert_eq!(
+            dates(r),
+            [
+                "2023-01-31",
+                "2023-02-28",
+                "2023-03-31",
+                "2023-04-30",
+                "2023-05-31"
+            ]
+        );
+        let r = MonthRange::new(
+            ymd(2023, 1, 31),
+            ymd(2023, 5, 31),
+            1,
+            false,
+            MonthDay::Clamp,
+        );
+        assert_eq!(r.len(), 4);
+        let r = MonthRange::new(ymd(2023, 2, 28), ymd(2023, 5, 1), 1, true, MonthDay::Clamp);
+        assert_eq!(dates(r), ["2023-02-28", "2023-03-28", "2023-04-28"]);
+        let r = MonthRange::new(
+            ymd(2023, 2, 28),
+            ymd(2023, 5, 1),
+            1,
+            true,
+            MonthDay::KeepMonthEnd,
+        );
+        assert_eq!(dates(r), ["2023-02-28", "2023-03-31", "2023-04-30"]);
+
+        // Every month end from 2023-01 through 2026-10
+        let r = MonthRange::new(
+            ymd(2023, 1, 31),
+            ymd(2026, 10, 31),
+            1,
+            true,
+            MonthDay::KeepMonthEnd,
+        );
+        assert_eq!(r.len(), 46);
+        assert_eq!(r.value_at(13), Some(ymd(2024, 2, 29)));
+        assert_eq!(r.into_iter().last(), Some(ymd(2026, 10, 31)));
+
+        // Day of the last month is past end
+        let r = MonthRange::new(ymd(2023, 1, 31), ymd(2023, 3, 15), 1, true, MonthDay::Clamp);
+        assert_eq!(dates(r), ["2023-01-31", "2023-02-28"]);
+        let r = MonthRange::new(
+            ymd(2023, 1, 15),
+            ymd(2023, 12, 31),
+            5,
+            true,
+            MonthDay::Clamp,
+        );
+        assert_eq!(dates(r), ["2023-01-15", "2023-06-15", "2023-11-15"]);
+
+        // Backward
+        let r = MonthRange::new(ymd(2023, 5, 31), ymd(2023, 2, 1), -1, true, MonthDay::Clamp);
+        assert_eq!(
+            dates(r),
+            ["2023-05-31", "2023-04-30", "2023-03-31", "2023-02-28"]
+        );
+        let r = MonthRange::new(
+            ymd(2023, 5, 10),
+            ymd(2023, 3, 10),
+            -1,
+            false,
+            MonthDay::Clamp,
+        );
+        assert_eq!(dates(r), ["2023-05-10", "2023-04-10"]);
+        let r = MonthRange::new(
+            ymd(2023, 5, 10),
+            ymd(2023, 3, 20),
+            -2,
+            true,
+            MonthDay::Clamp,
+        );
+        assert_eq!(dates(r), ["2023-05-10"]);
+
+        // Empty
+        assert!(MonthRange::new(
+            ymd(2023, 1, 10),
+            ymd(2023, 1, 20),
+            -1,
+            true,
+            MonthDay::Clamp
+        )
+        .is_empty());
+        assert!(MonthRange::new(
+            ymd(2023, 1, 10),
+            ymd(2023, 1, 10),
+            1,
+            false,
+            MonthDay::Clamp
+        )
+        .is_empty());
+        assert!(
+            MonthRange::new(
+                ymd(2023, 1, 31),
+                ymd(2023, 2, 27),
+                1,
+                false,
+                MonthDay::Clamp
+            )
+            .len()
+                == 1
+        );
+        assert_eq!(
+            MonthRange::new(ymd(2023, 1, 10), ymd(2023, 1, 10), 1, true, MonthDay::Clamp).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn year_range() {
+        let r = MonthRange::years(
+            ymd(2020, 2, 29),
+            ymd(2024, 12, 31),
+            1,
+            true,
+            MonthDay::Clamp,
+        );
+        assert_eq!(
+            dates(r),
+            [
+                "2020-02-29",
+                "2021-02-28",
+                "2022-02-28",
+                "2023-02-28",
+                "2024-02-29"
+            ]
+        );
+        let r = MonthRange::years(ymd(2024, 6, 1), ymd(2014, 6, 1), -5, false, MonthDay::Clamp);
+        assert_eq!(dates(r), ["2024-06-01", "2019-06-01"]);
+        let r = MonthRange::years(ymd(-1, 3, 1), ymd(1, 3, 1), 1, true, MonthDay::Clamp);
+        assert_eq!(r.len(), 3);
+    }
+
+    #[test]
+    fn indexed_month_range() {
+        let r = IndexedMonthRange::new(
+            ymd(2023, 11, 30),
+            ymd(2024, 2, 29),
+            1,
+            true,
+            MonthDay::KeepMonthEnd,
+        );
+        let values: Vec<_> = r.into_iter().collect();
+        assert_eq!(
+            values,
+            [
+                (0, ymd(2023, 11, 30)),
+                (1, ymd(2023, 12, 31)),
+                (2, ymd(2024, 1, 31)),
+                (3, ymd(2024, 2, 29))
+            ]
+        );
+        assert_eq!(r.into_iter().nth(3), Some((3, ymd(2024, 2, 29))));
+    }
+
+    #[test]
+    fn month_last_day() {
+        assert_eq!(last_day(2024, 1), 29);
+        assert_eq!(last_day(2100, 1), 28);
+        assert_eq!(last_day(2000, 1), 29);
+        assert_eq!(last_day(2023, 3), 30);
+        assert_eq!(last_day(2023, 11), 31);
+        assert_eq!(last_day(NaiveDate::MAX.year(), 11), 31);
+        assert_eq!(last_day(NaiveDate::MIN.year(), 0), 31);
+    }
+}
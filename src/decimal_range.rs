@@ -0,0 +1,231 @@
+use crate::basic_range::*;
+use crate::ratio_range::{decimal_ratio, ParseDecimalError};
+use core::fmt;
+use num::rational::Ratio;
+use num::CheckedMul;
+
+/// Decimal with *digits* fractional digits, stored as *scaled* = value * 10^digits
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decimal {
+    pub scaled: i64,
+    pub digits: u32,
+}
+
+impl Decimal {
+    /// Nearest f64, 0.15 is 0.15 and not 0.15000000000000002
+    pub fn to_f64(self) -> f64 {
+        // Powers of 10 through 10^22 are exact f64 products, powi() may round
+        let power = (0..self.digits).fold(1f64, |power, _| power * 10.0);
+        self.scaled as f64 / power
+    }
+}
+
+/// Always *digits* fractional digits, e.g. "0.50"
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let magnitude = self.scaled.unsigned_abs();
+        let sign = if self.scaled < 0 { "-" } else { "" };
+        if self.digits == 0 {
+            write!(f, "{}{}", sign, magnitude)
+        } else {
+            // More than 19 digits, all of magnitude is fraction
+            let (integer, fraction) = match 10u64.checked_pow(self.digits) {
+                Some(scale) => (magnitude / scale, magnitude % scale),
+                None => (0, magnitude),
+            };
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                integer,
+                fraction,
+                width = self.digits as usize
+            )
+        }
+    }
+}
+
+/// Range of decimals like "from 0.05 to 2.5 step 0.05", stepped exactly as scaled integers
+/// in a BasicRange<i64>, so the count is right and values don't drift
+#[derive(Clone, Copy, Debug)]
+pub struct DecimalRange {
+    pub scaled: BasicRange<i64>,
+    pub digits: u32,
+}
+
+impl DecimalRange {
+    /// Range of values already scaled by 10^*digits*, e.g. 5, 250, 5 with 2 digits for 0.05 to 2.5 step 0.05
+    pub fn from_scaled(start: i64, end: i64, step: i64, inclusive: bool, digits: u32) -> Self {
+        DecimalRange {
+            scaled: BasicRange::new(start, end, step, inclusive),
+            digits,
+        }
+    }
+
+    /// Range of decimal strings with *digits* fractional digits, inputs with more digits are an error
+    pub fn with_digits(
+        start: &str,
+        end: &str,
+        step: &str,
+        inclusive: bool,
+        digits: u32,
+    ) -> Result<Self, ParseDecimalError> {
+        let step = scale(step, digits)?;
+        if step == 0 {
+            return Err(ParseDecimalError::ZeroStep);
+        }
+        Ok(Self::from_scaled(
+            scale(start, digits)?,
+            scale(end, digits)?,
+            step,
+            inclusive,
+            digits,
+        ))
+    }
+
+    /// Same as with_digits(), digits are the most fractional digits of start, end and step
+    pub fn parse(
+        start: &str,
+        end: &str,
+        step: &str,
+        inclusive: bool,
+    ) -> Result<Self, ParseDecimalError> {
+        let digits = [start, end, step]
+            .iter()
+            .map(|s| s.split_once('.').map_or(0, |(_, fraction)| fraction.len()))
+            .max()
+            .unwrap_or(0) as u32;
+        Self::with_digits(start, end, step, inclusive, digits)
+    }
+
+    pub fn len(&self) -> usize {
+        self.scaled.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scaled.is_empty()
+    }
+
+    /// Value at *index*, None if index is not below len()
+    pub fn value_at(&self, index: usize) -> Option<Decimal> {
+        let digits = self.digits;
+        self.scaled
+            .value_at(index)
+            .map(|scaled| Decimal { scaled, digits })
+    }
+
+    /// Values as nearest f64
+    pub fn to_f64s(self) -> impl Iterator<Item = f64> {
+        self.into_iter().map(Decimal::to_f64)
+    }
+
+    /// Values formatted with digits fractional digits, e.g. "0.05", "0.10"
+    #[cfg(feature = "alloc")]
+    pub fn to_strings(self) -> impl Iterator<Item = alloc::string::String> {
+        use alloc::string::ToString;
+        self.into_iter().map(|d| d.to_string())
+    }
+}
+
+/// *s* * 10^*digits* as an integer
+fn scale(s: &str, digits: u32) -> Result<i64, ParseDecimalError> {
+    let factor = 10i64
+        .checked_pow(digits)
+        .ok_or(ParseDecimalError::Overflow)?;
+    let scaled = CheckedMul::checked_mul(&decimal_ratio::<i64>(s)?, &Ratio::from_integer(factor))
+        .ok_or(ParseDecimalError::Overflow)?;
+    if scaled.is_integer() {
+        Ok(scaled.to_integer())
+    } else {
+        Err(ParseDecimalError::TooManyDigits)
+    }
+}
+
+impl IntoIterator for DecimalRange {
+    type Item = Decimal;
+    type IntoIter = DecimalRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        DecimalRangeIter {
+            scaled: self.scaled.into_iter(),
+            digits: self.digits,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DecimalRangeIter {
+    scaled: BasicRangeIter<i64>,
+    digits: u32,
+}
+
+impl Iterator for DecimalRangeIter {
+    type Item = Decimal;
+
+    fn next(&mut self) -> Option<Decimal> {
+        let digits = self.digits;
+        self.scaled.next().map(|scaled| Decimal { scaled, digits })
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn decimal_range() {
+        let r = DecimalRange::parse("0.05", "2.5", "0.05", true).unwrap();
+        assert_eq!(r.digits, 2);
+        assert_eq!(r.len(), 50);
+        let values: Vec<f64> = r.to_f64s().collect();
+        assert_eq!(values.len(), 50);
+        assert_eq!(values[2], 0.15);
+        assert_eq!(values[49], 2.5);
+        let strings: Vec<String> = r.to_strings().collect();
+        assert_eq!(strings[..3], ["0.05", "0.10", "0.15"]);
+        assert_eq!(strings[49], "2.50");
+        assert_eq!(
+            r.value_at(19).map(|d| d.to_string()),
+            Some("1.00".to_string())
+        );
+
+        let r = DecimalRange::parse("1", "-1", "-0.5", false).unwrap();
+        let strings: Vec<String> = r.to_strings().collect();
+        assert_eq!(strings, ["1.0", "0.5", "0.0", "-0.5"]);
+        let r = DecimalRange::with_digits("0", "0.1", "0.03", true, 3).unwrap();
+        let strings: Vec<String> = r.to_strings().collect();
+        assert_eq!(strings, ["0.000", "0.030", "0.060", "0.090"]);
+        let r = DecimalRange::from_scaled(-3, 3, 3, true, 0);
+        assert_eq!(r.to_strings().collect::<Vec<_>>(), ["-3", "0", "3"]);
+        let tiny = Decimal {
+            scaled: -12,
+            digits: 21,
+        };
+        assert_eq!(tiny.to_string(), "-0.000000000000000000012");
+    }
+
+    #[test]
+    fn decimal_range_errors() {
+        assert_eq!(
+            DecimalRange::with_digits("0", "1", "0.125", true, 2).unwrap_err(),
+            ParseDecimalError::TooManyDigits
+        );
+        assert_eq!(
+            DecimalRange::parse("0", "1", "zero", true).unwrap_err(),
+            ParseDecimalError::Invalid
+        );
+        assert_eq!(
+            DecimalRange::with_digits("0", "1", "0.1", true, 19).unwrap_err(),
+            ParseDecimalError::Overflow
+        );
+        assert_eq!(
+            DecimalRange::parse("0", "1", "0.00", true).unwrap_err(),
+            ParseDecimalError::ZeroStep
+        );
+        assert_eq!(
+            DecimalRange::with_digits("0", "1", "-0", false, 2).unwrap_err(),
+            ParseDecimalError::ZeroStep
+        );
+    }
+}
@@ -49,6 +49,7 @@ mod edge_test {
         }
     }
 
+    use num::Zero;
     use std::any::type_name;
     use std::ops::Rem;
 
@@ -81,7 +82,7 @@ mod edge_test {
             };
             // countup
             print!(
-                "{} while range [{}, {}{}, step {}",
+                "{} while range [{:?}, {:?}{}, step {}",
                 type_name::<T>(),
                 int_min,
                 int_max,
@@ -126,7 +127,7 @@ mod edge_test {
             };
 
             print!(
-                "Backward {} while range {}{}, {}], step: {}:\n",
+                "Backward {} while range {}{:?}, {:?}], step: {}:\n",
                 type_name::<T>(),
                 get_range_begin_mark_char(inclusive),
                 int_min,
@@ -445,4 +446,92 @@ mod edge_test {
     fn test_u32_inclusive_edge_on_step() {
         int_edge_with_step::<u32>(true, 5 + 1 + i16::MAX as i32 - i16::MIN as i32);
     }
+
+    /// Steps of *magnitude* over the full domain, magnitude may be up to max - min
+    fn int_edge_with_magnitude<T: crate::basic_range::IteratorOps>(
+        inclusive: bool,
+        magnitude: T::ExtendedStep,
+    ) {
+        let zero = T::ExtendedStep::zero();
+        let int_min: T = T::min();
+        let int_max: T = T::max();
+        let range_size: T::ExtendedStep = int_max.to_extended_step() - int_min.to_extended_step();
+        let steps = (range_size / magnitude).floor().to_usize();
+        let on_step = range_size.rem(magnitude) == zero;
+        let len = if inclusive || !on_step {
+            steps + 1
+        } else {
+            steps
+        };
+        println!(
+            "{} while range [{:?}, {:?}{}, step magnitude {}, len {}",
+            type_name::<T>(),
+            int_min,
+            int_max,
+            get_range_end_mark_char(inclusive),
+            magnitude,
+            len
+        );
+
+        for forward in [true, false] {
+            let (start, end, step) = if forward {
+                (int_min, int_max, magnitude)
+            } else {
+                (int_max, int_min, zero - magnitude)
+            };
+            let range =
+                IndexedRange::<T>::with_magnitude(start, end, magnitude, forward, inclusive);
+            let mut index: usize = 0;
+            for (i, value) in range {
+                assert_eq!(i, index);
+                let expected = start.to_extended_step() + T::ExtendedStep::from_usize(i) * step;
+                assert_eq!(value.to_extended_step(), expected);
+                index += 1;
+            }
+            assert_eq!(index, len);
+            let range = BasicRange::<T>::with_magnitude(start, end, magnitude, forward, inclusive);
+            assert_eq!(range.len(), len);
+            assert!(range.contains(start));
+            assert_eq!(range.contains(end), inclusive && on_step);
+        }
+    }
+
+    macro_rules! int_edge_full_width {
+        ($($name:ident: $t:ty => $extended:ty);* $(;)?) => {$(
+            #[test]
+            fn $name() {
+                let width = <$t>::MAX as $extended - <$t>::MIN as $extended;
+                for magnitude in [width, width - 1, width / 2, width / 2 + 1, width / 3 + 1] {
+                    int_edge_with_magnitude::<$t>(false, magnitude);
+                    int_edge_with_magnitude::<$t>(true, magnitude);
+                }
+            }
+        )*};
+    }
+
+    int_edge_full_width! {
+        test_i8_full_width_step: i8 => i16;
+        test_u8_full_width_step: u8 => i16;
+        test_i16_full_width_step: i16 => i32;
+        test_u16_full_width_step: u16 => i32;
+        test_i32_full_width_step: i32 => i64;
+        test_u32_full_width_step: u32 => i64;
+        test_i64_full_width_step: i64 => i128;
+        test_u64_full_width_step: u64 => i128;
+        test_isize_full_width_step: isize => i128;
+        test_usize_full_width_step: usize => i128;
+    }
+
+    #[test]
+    fn test_128_full_width_step() {
+        let width = ExtendedI128::from_u128(u128::MAX);
+        let one = ExtendedI128::from_u128(1);
+        let two = ExtendedI128::from_u128(2);
+        for magnitude in [width, width - one, width / two, width / two + one] {
+            int_edge_with_magnitude::<u128>(false, magnitude);
+            int_edge_with_magnitude::<u128>(true, magnitude);
+            int_edge_with_magnitude::<i128>(false, magnitude);
+            int_edge_with_magnitude::<i128>(true, magnitude);
+        }
+    }
 }
@@ -7,7 +7,10 @@ pub struct IndexedRange<T>
 where
     T: IteratorOps,
 {
-    basic_range: BasicRange<T>,
+    pub(crate) basic_range: BasicRange<T>,
+    /// Index of the first value and between values, in the whole range for a part()
+    pub(crate) first_index: usize,
+    pub(crate) index_step: usize,
 }
 
 impl<T> IndexedRange<T>
@@ -20,8 +23,79 @@ where
         // }
         IndexedRange {
             basic_range: BasicRange::<T>::new(start, end, step, inclusive),
+            first_index: 0,
+            index_step: 1,
         }
     }
+
+    /// Same as BasicRange::with_magnitude()
+    pub fn with_magnitude(
+        start: T,
+        end: T,
+        magnitude: T::ExtendedStep,
+        forward: bool,
+        inclusive: bool,
+    ) -> Self {
+        IndexedRange {
+            basic_range: BasicRange::<T>::with_magnitude(start, end, magnitude, forward, inclusive),
+            first_index: 0,
+            index_step: 1,
+        }
+    }
+
+    /// Same as BasicRange::part(), yielding the indices of the whole range
+    pub fn part(&self, i: usize, k: usize) -> Self {
+        let first = self.basic_range.part_bounds(i, k).0;
+        let first = first.checked_to_usize().expect("part index beyond usize");
+        IndexedRange {
+            basic_range: self.basic_range.part(i, k),
+            first_index: self.global_index(first),
+            index_step: self.index_step,
+        }
+    }
+
+    /// Same as BasicRange::interleave_part(), yielding the indices of the whole range,
+    /// values whose index would be beyond usize are left out, e.g. parts of parts
+    pub fn interleave_part(&self, i: usize, k: usize) -> Self {
+        let part = self.basic_range.interleave_part(i, k);
+        let (basic_range, first_index, index_step) =
+            match (self.checked_global_index(i), self.index_step.checked_mul(k)) {
+                (Some(first_index), Some(index_step)) => (part, first_index, index_step),
+                // The second value's index is already beyond usize
+                (Some(first_index), None) => (part.take(1), first_index, self.index_step),
+                (None, _) => (part.take(0), 0, self.index_step),
+            };
+        IndexedRange {
+            basic_range,
+            first_index,
+            index_step,
+        }
+    }
+
+    /// Index yielded with value *local* of interleave_part(i, k), None beyond the range
+    pub fn interleave_index(&self, i: usize, k: usize, local: usize) -> Option<usize> {
+        self.basic_range
+            .interleave_index(i, k, local)
+            .and_then(|index| self.checked_global_index(index))
+    }
+
+    /// Index yielded with the value at *index*
+    fn global_index(&self, index: usize) -> usize {
+        self.first_index + index * self.index_step
+    }
+
+    /// Same as global_index(), None if it's beyond usize
+    fn checked_global_index(&self, index: usize) -> Option<usize> {
+        index
+            .checked_mul(self.index_step)
+            .and_then(|offset| self.first_index.checked_add(offset))
+    }
+
+    /// All *k* parts of part()
+    #[cfg(feature = "alloc")]
+    pub fn split_into(&self, k: usize) -> alloc::vec::Vec<Self> {
+        (0..k).map(|i| self.part(i, k)).collect()
+    }
 }
 
 pub struct IndexedRangeIter<T>
@@ -30,6 +104,7 @@ where
 {
     basic_range_iter: BasicRangeIter<T>,
     index: usize,
+    index_step: usize,
 }
 
 // Implement the Iterator trait for IndexedRangeIter
@@ -43,7 +118,8 @@ where
         match self.basic_range_iter.next() {
             Some(t) => {
                 let result = (self.index, t);
-                self.index += 1;
+                // Wraps only past the last value of parts of parts
+                self.index = self.index.wrapping_add(self.index_step);
                 Some(result)
             }
             None => None,
@@ -61,21 +137,18 @@ where
 
     fn into_iter(self) -> Self::IntoIter {
         IndexedRangeIter {
-            basic_range_iter: BasicRangeIter::<T> {
-                current: self.basic_range.start,
-                end: self.basic_range.end,
-                step: self.basic_range.step,
-
-                inclusive_or_not_on_step: self.basic_range.inclusive_or_not_on_step,
-                invalid_range: self.basic_range.invalid_range,
-            },
-            index: 0,
+            basic_range_iter: self.basic_range.into_iter(),
+            index: self.first_index,
+            index_step: self.index_step,
         }
     }
 }
 
 #[macro_export]
 macro_rules! indexed_range_exclusive {
+    ($start:expr, $end:expr) => {
+        $crate::indexed_rangex_exclusive!($start, $end)
+    };
     ($typename:ty, $start:expr, $end:expr) => {
         indexed_range_exclusive!($typename, $start, $end, 1)
     };
@@ -86,6 +159,9 @@ macro_rules! indexed_range_exclusive {
 
 #[macro_export]
 macro_rules! indexed_range_inclusive {
+    ($start:expr, $end:expr) => {
+        $crate::indexed_rangex_inclusive!($start, $end)
+    };
     ($typename:ty, $start:expr, $end:expr) => {
         indexed_range_inclusive!($typename, $start, $end, 1)
     };
@@ -94,6 +170,28 @@ macro_rules! indexed_range_inclusive {
     };
 }
 
+/// Indexed counterpart of `rangex_exclusive!`, type inferred, with or without step
+#[macro_export]
+macro_rules! indexed_rangex_exclusive {
+    ($start:expr, $end:expr) => {
+        $crate::indexed_rangex_exclusive!($start, $end, 1)
+    };
+    ($start:expr, $end:expr, $step:expr) => {
+        $crate::indexed_range::IndexedRange::new($start, $end, $step, false)
+    };
+}
+
+/// Indexed counterpart of `rangex_inclusive!`, type inferred, with or without step
+#[macro_export]
+macro_rules! indexed_rangex_inclusive {
+    ($start:expr, $end:expr) => {
+        $crate::indexed_rangex_inclusive!($start, $end, 1)
+    };
+    ($start:expr, $end:expr, $step:expr) => {
+        $crate::indexed_range::IndexedRange::new($start, $end, $step, true)
+    };
+}
+
 #[cfg(test)]
 fn verify_indexed_range<T>(expect: &Vec<T>, r: IndexedRange<T>)
 where
@@ -118,6 +216,7 @@ where
 #[cfg(test)]
 mod main_test {
     use super::*;
+    use crate::rangex;
 
     #[test]
     fn basic_1() {
@@ -165,6 +264,24 @@ mod main_test {
         verify_indexed_range(&expect, indexed_range_inclusive!(i32, 0, 3, 2));
     }
 
+    #[test]
+    fn inferred_type() {
+        let (start, end) = (0, 2);
+        verify_indexed_range(&vec![0, 1, 2], indexed_range_inclusive!(start, end));
+        verify_indexed_range(&vec![0, 1], indexed_range_exclusive!(start, end));
+        verify_indexed_range(&vec![0, 2], indexed_rangex_inclusive!(start, end, 2));
+        verify_indexed_range(&vec![0], indexed_rangex_exclusive!(start, end, 2));
+        verify_indexed_range(&vec![], indexed_rangex_inclusive!(end, start));
+        verify_indexed_range(&vec![2u8, 1], indexed_rangex_exclusive!(2u8, 0, -1));
+    }
+
+    #[test]
+    fn rangex_dsl() {
+        verify_indexed_range(&vec![0, 1, 2], rangex!(indexed 0 ..= 2));
+        verify_indexed_range(&vec![9, 6, 3], rangex!(indexed 9 downto 1 by 3));
+        verify_indexed_range(&vec![1u8, 5, 9], rangex!(indexed u8: 1 .. 10, step 4));
+    }
+
     #[test]
     //fn void_range_prop(inclusive in 0..=1) {
     fn void_range_prop() {
@@ -246,6 +363,59 @@ mod main_test {
         }
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn parts() {
+        let r = IndexedRange::<u8>::new(10, 0, -2, true);
+        let parts: Vec<Vec<(usize, u8)>> = r
+            .split_into(4)
+            .into_iter()
+            .map(|p| p.into_iter().collect())
+            .collect();
+        assert_eq!(
+            parts,
+            [
+                vec![(0, 10), (1, 8)],
+                vec![(2, 6), (3, 4)],
+                vec![(4, 2)],
+                vec![(5, 0)]
+            ]
+        );
+        let nested: Vec<_> = r.part(0, 2).part(1, 2).into_iter().collect();
+        assert_eq!(nested, [(2, 6)]);
+
+        let odd = r.interleave_part(1, 2);
+        assert_eq!(odd.interleave_index(0, 2, 1), Some(5));
+        let odd: Vec<_> = odd.into_iter().collect();
+        assert_eq!(odd, [(1, 8), (3, 4), (5, 0)]);
+        assert_eq!(r.interleave_index(1, 2, 2), Some(5));
+        assert_eq!(r.interleave_index(1, 2, 3), None);
+        let nested: Vec<_> = r
+            .interleave_part(1, 2)
+            .interleave_part(0, 2)
+            .into_iter()
+            .collect();
+        assert_eq!(nested, [(1, 8), (5, 0)]);
+        let nested: Vec<_> = r.interleave_part(0, 2).part(1, 2).into_iter().collect();
+        assert_eq!(nested, [(4, 2)]);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn interleave_beyond_usize() {
+        // Indices step by 2^63, the part of the part would step by 2^64
+        let r = IndexedRange::<u64>::new(0, u64::MAX, 1, true).interleave_part(1, 1 << 63);
+        let nested: Vec<_> = r.interleave_part(0, 2).into_iter().collect();
+        assert_eq!(nested, [(1, 1)]);
+        let nested: Vec<_> = r.interleave_part(1, 2).into_iter().collect();
+        assert_eq!(nested, [((1 << 63) + 1, (1 << 63) + 1)]);
+        assert!(r.interleave_part(3, 4).basic_range.is_empty());
+        assert_eq!(r.interleave_index(1, 2, 0), Some((1 << 63) + 1));
+        assert_eq!(r.interleave_index(0, 2, 1), None);
+        let values: Vec<_> = r.into_iter().collect();
+        assert_eq!(values, [(1, 1), ((1 << 63) + 1, (1 << 63) + 1)]);
+    }
+
     #[test]
     #[should_panic]
     fn zero_step() {
@@ -0,0 +1,350 @@
+use crate::basic_range::*;
+use core::fmt;
+use core::net::{Ipv4Addr, Ipv6Addr};
+use core::str::FromStr;
+
+/// Addresses step like their integer representation
+impl RangeStep for Ipv4Addr {
+    type Position = u32;
+    fn to_position(self) -> u32 {
+        self.to_bits()
+    }
+    fn from_position(position: u32) -> Self {
+        Ipv4Addr::from_bits(position)
+    }
+}
+
+impl RangeStep for Ipv6Addr {
+    type Position = u128;
+    fn to_position(self) -> u128 {
+        self.to_bits()
+    }
+    fn from_position(position: u128) -> Self {
+        Ipv6Addr::from_bits(position)
+    }
+}
+
+/// Why a CIDR block can't be created
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CidrError {
+    /// No `/` between address and prefix length
+    MissingPrefixLen,
+    /// Address part doesn't parse
+    InvalidAddress,
+    /// Prefix length doesn't parse or is longer than the address
+    InvalidPrefixLen,
+}
+
+impl fmt::Display for CidrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CidrError::MissingPrefixLen => "missing prefix length",
+            CidrError::InvalidAddress => "invalid address",
+            CidrError::InvalidPrefixLen => "invalid prefix length",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CidrError {}
+
+/// CIDR blocks for Ipv4Cidr and Ipv6Cidr, all differences are in hosts()
+macro_rules! impl_cidr {
+    ($($(#[$doc:meta])* $cidr:ident, $addr:ty, $bits:ty, $subnets:ident, $skip_broadcast:literal);* $(;)?) => {$(
+        $(#[$doc])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub struct $cidr {
+            network: $addr,
+            prefix_len: u8,
+        }
+
+        impl $cidr {
+            /// Block of *address* with *prefix_len* leading bits, host bits of address are cleared
+            pub fn new(address: $addr, prefix_len: u8) -> Result<Self, CidrError> {
+                if prefix_len as u32 > <$bits>::BITS {
+                    return Err(CidrError::InvalidPrefixLen);
+                }
+                Ok($cidr {
+                    network: <$addr>::from_bits(address.to_bits() & Self::mask(prefix_len)),
+                    prefix_len,
+                })
+            }
+
+            fn mask(prefix_len: u8) -> $bits {
+                <$bits>::MAX
+                    .checked_shl(<$bits>::BITS - prefix_len as u32)
+                    .unwrap_or(0)
+            }
+
+            pub fn network(&self) -> $addr {
+                self.network
+            }
+
+            pub fn prefix_len(&self) -> u8 {
+                self.prefix_len
+            }
+
+            /// Last address of the block
+            pub fn broadcast(&self) -> $addr {
+                <$addr>::from_bits(self.network.to_bits() | !Self::mask(self.prefix_len))
+            }
+
+            pub fn contains(&self, address: $addr) -> bool {
+                address.to_bits() & Self::mask(self.prefix_len) == self.network.to_bits()
+            }
+
+            /// All addresses of the block from network through broadcast, by *step*,
+            /// use checked_len() or len_extended() for blocks wider than usize
+            pub fn addresses(&self, step: <$bits as IteratorOps>::Step) -> BasicRange<$addr> {
+                BasicRange::new(self.network, self.broadcast(), step, true)
+            }
+
+            /// Addresses without network and broadcast, by *step*, except for blocks of 1 or 2 addresses
+            pub fn hosts(&self, step: <$bits as IteratorOps>::Step) -> BasicRange<$addr> {
+                if self.prefix_len as u32 + 1 >= <$bits>::BITS {
+                    return self.addresses(step);
+                }
+                let first = <$addr>::from_bits(self.network.to_bits() + 1);
+                let last = <$addr>::from_bits(self.broadcast().to_bits() - $skip_broadcast);
+                BasicRange::new(first, last, step, true)
+            }
+
+            /// Blocks of *prefix_len* inside this block, e.g. /24s inside a /16
+            pub fn subnets(&self, prefix_len: u8) -> Result<$subnets, CidrError> {
+                if prefix_len < self.prefix_len || prefix_len as u32 > <$bits>::BITS {
+                    return Err(CidrError::InvalidPrefixLen);
+                }
+                let count_bits = (prefix_len - self.prefix_len) as u32;
+                let last = <$bits>::MAX.checked_shr(<$bits>::BITS - count_bits).unwrap_or(0);
+                Ok($subnets {
+                    indexes: BasicRange::new(0, last, 1, true).into_iter(),
+                    network: self.network.to_bits(),
+                    prefix_len,
+                })
+            }
+        }
+
+        impl FromStr for $cidr {
+            type Err = CidrError;
+
+            /// Parses `address/prefix_len`, e.g. `10.0.0.0/22`
+            fn from_str(s: &str) -> Result<Self, CidrError> {
+                let (address, prefix_len) = s.split_once('/').ok_or(CidrError::MissingPrefixLen)?;
+                let address = address.parse().map_err(|_| CidrError::InvalidAddress)?;
+                let prefix_len = prefix_len.parse().map_err(|_| CidrError::InvalidPrefixLen)?;
+                $cidr::new(address, prefix_len)
+            }
+        }
+
+        impl fmt::Display for $cidr {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}/{}", self.network, self.prefix_len)
+            }
+        }
+
+        impl IntoIterator for $cidr {
+            type Item = $addr;
+            type IntoIter = BasicRangeIter<$addr>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.addresses(1).into_iter()
+            }
+        }
+
+        /// Iterator of subnets from subnets(), in address order
+        #[derive(Clone, Debug)]
+        pub struct $subnets {
+            indexes: BasicRangeIter<$bits>,
+            network: $bits,
+            prefix_len: u8,
+        }
+
+        impl Iterator for $subnets {
+            type Item = $cidr;
+
+            fn next(&mut self) -> Option<$cidr> {
+                let index = self.indexes.next()?;
+                let host_bits = <$bits>::BITS - self.prefix_len as u32;
+                let offset = index.checked_shl(host_bits).unwrap_or(0);
+                Some($cidr {
+                    network: <$addr>::from_bits(self.network | offset),
+                    prefix_len: self.prefix_len,
+                })
+            }
+        }
+    )*};
+}
+
+impl_cidr! {
+    /// IPv4 block like `192.168.0.0/16`, hosts() skips network and broadcast addresses
+    Ipv4Cidr, Ipv4Addr, u32, Ipv4Subnets, 1;
+    /// IPv6 block like `2001:db8::/32`, hosts() skips the subnet-router anycast address,
+    /// IPv6 has no broadcast
+    Ipv6Cidr, Ipv6Addr, u128, Ipv6Subnets, 0;
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+    use crate::indexed_range::IndexedRange;
+
+    fn v4(s: &str) -> Ipv4Addr {
+        s.parse().unwrap()
+    }
+
+    fn v6(s: &str) -> Ipv6Addr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn address_range() {
+        let r = BasicRange::new(v4("192.168.1.10"), v4("192.168.1.200"), 1, true);
+        assert_eq!(r.len(), 191);
+        assert_eq!(r.into_iter().last(), Some(v4("192.168.1.200")));
+        let r: Vec<_> = BasicRange::new(v4("10.0.0.254"), v4("10.0.1.2"), 2, true)
+            .into_iter()
+            .collect();
+        assert_eq!(r, [v4("10.0.0.254"), v4("10.0.1.0"), v4("10.0.1.2")]);
+        let r: Vec<_> = BasicRange::new(Ipv4Addr::BROADCAST, v4("255.255.255.253"), -1, true)
+            .into_iter()
+            .collect();
+        assert_eq!(r.len(), 3);
+
+        let r: Vec<_> = BasicRange::new(v6("::fffe"), v6("::1:1"), 1, false)
+            .into_iter()
+            .collect();
+        assert_eq!(r, [v6("::fffe"), v6("::ffff"), v6("::1:0")]);
+        let last = v6("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff");
+        let r = BasicRange::new(Ipv6Addr::UNSPECIFIED, last, i128::MAX, true);
+        assert_eq!(r.len(), 3);
+        assert_eq!(
+            r.value_at(1),
+            Some(v6("7fff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"))
+        );
+
+        let indexed: Vec<_> = IndexedRange::new(v4("10.0.0.1"), v4("10.0.0.3"), 1, true)
+            .into_iter()
+            .collect();
+        assert_eq!(
+            indexed,
+            [
+                (0, v4("10.0.0.1")),
+                (1, v4("10.0.0.2")),
+                (2, v4("10.0.0.3"))
+            ]
+        );
+    }
+
+    #[test]
+    fn cidr() {
+        let block: Ipv4Cidr = "10.0.0.0/22".parse().unwrap();
+        assert_eq!(block.broadcast(), v4("10.0.3.255"));
+        assert_eq!(block.addresses(4).len(), 256);
+        assert_eq!(block.addresses(4).into_iter().nth(65), Some(v4("10.0.1.4")));
+        assert_eq!(block.hosts(1).len(), 1022);
+        assert_eq!(block.hosts(1).value_at(0), Some(v4("10.0.0.1")));
+        assert!(block.hosts(1).contains(v4("10.0.3.254")));
+        assert!(!block.hosts(1).contains(v4("10.0.3.255")));
+        assert_eq!(block.into_iter().count(), 1024);
+        assert!(block.contains(v4("10.0.2.7")) && !block.contains(v4("10.0.4.0")));
+
+        assert_eq!(
+            Ipv4Cidr::new(v4("192.168.1.77"), 24),
+            "192.168.1.0/24".parse()
+        );
+        assert_eq!(
+            Ipv4Cidr::new(v4("1.2.3.4"), 0).unwrap().broadcast(),
+            Ipv4Addr::BROADCAST
+        );
+        assert_eq!(Ipv4Cidr::new(v4("1.2.3.4"), 32).unwrap().hosts(1).len(), 1);
+        assert_eq!(Ipv4Cidr::new(v4("1.2.3.4"), 31).unwrap().hosts(1).len(), 2);
+        assert_eq!(
+            "10.0.0.0/33".parse::<Ipv4Cidr>(),
+            Err(CidrError::InvalidPrefixLen)
+        );
+        assert_eq!(
+            "10.0.0/8".parse::<Ipv4Cidr>(),
+            Err(CidrError::InvalidAddress)
+        );
+        assert_eq!(
+            "10.0.0.0".parse::<Ipv4Cidr>(),
+            Err(CidrError::MissingPrefixLen)
+        );
+        assert_eq!(block.to_string(), "10.0.0.0/22");
+
+        let block: Ipv6Cidr = "2001:db8::/126".parse().unwrap();
+        let hosts: Vec<_> = block.hosts(1).into_iter().collect();
+        assert_eq!(
+            hosts,
+            [v6("2001:db8::1"), v6("2001:db8::2"), v6("2001:db8::3")]
+        );
+        let all = Ipv6Cidr::new(Ipv6Addr::LOCALHOST, 0).unwrap();
+        assert_eq!(all.addresses(i128::MAX).len(), 3);
+    }
+
+    #[test]
+    fn wide_cidr() {
+        let block: Ipv6Cidr = "2001:db8::/64".parse().unwrap();
+        let hosts = block.hosts(1);
+        assert_eq!(hosts.len(), usize::MAX);
+        assert_eq!(hosts.first(), Some(v6("2001:db8::1")));
+        assert_eq!(hosts.last(), Some(v6("2001:db8::ffff:ffff:ffff:ffff")));
+        assert!(hosts.contains(v6("2001:db8::ffff:0:0:1")));
+        assert!(!hosts.contains(v6("2001:db8:0:1::")));
+        assert_eq!(block.addresses(1).checked_len(), None);
+        assert_eq!(block.addresses(1).len_extended().to_u128(), Some(1 << 64));
+        assert_eq!(block.addresses(1 << 32).len(), 1 << 32);
+
+        let block: Ipv6Cidr = "2001:db8::/48".parse().unwrap();
+        let hosts = block.hosts(1);
+        assert_eq!(hosts.checked_len(), None);
+        assert_eq!(hosts.len_extended().to_u128(), Some((1 << 80) - 1));
+        assert_eq!(
+            hosts.last(),
+            Some(v6("2001:db8:0:ffff:ffff:ffff:ffff:ffff"))
+        );
+        assert!(hosts.contains(v6("2001:db8:0:8000::")));
+        assert!(!hosts.contains(v6("2001:db8:1::")));
+        assert_eq!(
+            hosts.value_at_extended(ExtendedI128::from(1u128 << 64)),
+            Some(v6("2001:db8:0:1::1"))
+        );
+        assert_eq!(hosts.into_iter().nth(2), Some(v6("2001:db8::3")));
+    }
+
+    #[test]
+    fn subnets() {
+        let block: Ipv4Cidr = "172.16.0.0/16".parse().unwrap();
+        let subnets: Vec<_> = block.subnets(24).unwrap().collect();
+        assert_eq!(subnets.len(), 256);
+        assert_eq!(subnets[1].to_string(), "172.16.1.0/24");
+        assert_eq!(subnets[255].broadcast(), v4("172.16.255.255"));
+        assert_eq!(block.subnets(16).unwrap().collect::<Vec<_>>(), [block]);
+        assert!(block.subnets(15).is_err());
+        assert_eq!(
+            Ipv4Cidr::new(Ipv4Addr::UNSPECIFIED, 0)
+                .unwrap()
+                .subnets(1)
+                .unwrap()
+                .nth(1),
+            "128.0.0.0/1".parse().ok()
+        );
+
+        let block: Ipv6Cidr = "2001:db8::/32".parse().unwrap();
+        let subnets: Vec<_> = block.subnets(34).unwrap().map(|s| s.to_string()).collect();
+        assert_eq!(
+            subnets,
+            [
+                "2001:db8::/34",
+                "2001:db8:4000::/34",
+                "2001:db8:8000::/34",
+                "2001:db8:c000::/34"
+            ]
+        );
+        let mut all = Ipv6Cidr::new(Ipv6Addr::UNSPECIFIED, 0)
+            .unwrap()
+            .subnets(128)
+            .unwrap();
+        assert_eq!(all.nth(2).map(|s| s.network()), Some(v6("::2")));
+    }
+}
@@ -30,7 +30,148 @@
 //! **indexed_range_inclusive!(*type*, *start*, *stop*, *step*)**
 //!
 //! Creates indexed range for *type* from *start* **through** *stop*, by *step*
-//
+//!
+//!
+//! **indexed_range_inclusive!(*start*, *stop*)**
+//!
+//! Creates indexed range from *start* **through** *stop*, by **step 1**, type inferred
+//!
+//!
+//! **rangex_inclusive!(*start*, *stop*)**, **rangex_inclusive!(*start*, *stop*, *step*)**
+//!
+//! Creates range from *start* **through** *stop*, type inferred, always stepped range instead of std range,
+//! also **rangex_exclusive!**, **indexed_rangex_inclusive!** and **indexed_rangex_exclusive!**
+//!
+//!
+//! **rangex!(*start* ..= *stop*, step *step*)**, **rangex!(*start* downto *stop* by *step*)**, **rangex!(*start* downuntil *stop*)**
+//!
+//! Spells out direction and inclusiveness, optionally with *type*: and `indexed` prefixes,
+//! e.g. `rangex!(indexed u8: 1 .. 10, step 3)`
+//!
+//!
+//! **len, checked_len, len_extended**
+//!
+//! `len()` panics when the count is beyond usize, e.g. all u64 values, `checked_len()` returns None instead,
+//! and `len_extended()` and `value_at_extended()` count any range exactly in the extended step type
+//!
+//!
+//! **BasicRange::with_magnitude(*start*, *stop*, *magnitude*, *forward*, *inclusive*)**
+//!
+//! Steps wider than the step type, up to the full width of the type,
+//! e.g. `BasicRange::<u8>::with_magnitude(0, 255, 200, true, true)` yields 0 and 200
+//!
+//!
+//! **BasicRange::from_mixed(*start*, *stop*, *step*, *inclusive*, *policy*)**
+//!
+//! Endpoints and step of other integer types, checked or clamped into the range's type,
+//! e.g. u32 start and usize end for `BasicRange<u64>`, `try_cast::<U>()` and `cast_lossy::<F>()`
+//! convert whole ranges to another integer or float type with the same len()
+//!
+//!
+//! **shift, scale, negate, affine, reversed**
+//!
+//! Derived ranges like `range.affine(4, base)` are BasicRange again, with len(), contains() and value_at(),
+//! errors instead of overflow
+//!
+//!
+//! **take, skip, split_at, sub, clamp_to**
+//!
+//! Parts of a range as BasicRange in O(1), `range.sub(Some(2), Some(10), 3)` is Python's `range[2:10:3]`
+//!
+//!
+//! **chunks, rchunks**
+//!
+//! Batches of at most N values, each a BasicRange, e.g. `range.chunks(1000)` for database writes
+//!
+//!
+//! **split_into, part**
+//!
+//! K contiguous parts whose lengths differ by at most one, `part(i, k)` for worker i alone,
+//! and IndexedRange parts keep the indices of the whole range
+//!
+//!
+//! **interleave_part**
+//!
+//! Round robin share of worker i of k, values at indices i, i + k, i + 2k, ... as a BasicRange,
+//! `interleave_index` maps a worker's local index back to the whole range
+//!
+//!
+//! **#[derive(RangeStep)]**
+//!
+//! With feature `derive`, newtypes like `struct UserId(u64)` and fieldless enums can be stepped,
+//! e.g. `range_inclusive!(Weekday, Weekday::Mon, Weekday::Fri, 2)`
+//!
+//!
+//! **char**
+//!
+//! chars step through valid scalar values, so `range_inclusive!(char, '\u{D7FF}', '\u{E000}')` has 2 values
+//!
+//!
+//! **Ipv4Addr, Ipv6Addr**
+//!
+//! Addresses step like their u32/u128 bits, `Ipv4Cidr`/`Ipv6Cidr` iterate blocks like `10.0.0.0/22`,
+//! their hosts and subnets
+//!
+//!
+//! **Duration, SystemTime, Instant**
+//!
+//! Step by a Duration in exact nanoseconds, `BasicRange::by_duration`, `BasicRange::aligned` and `InstantRange`
+//!
+//!
+//! **Dates**
+//!
+//! With feature `chrono`, `NaiveDate` steps by days, and `MonthRange` by months or years with end of month clamping
+//!
+//!
+//! **BigInt, BigUint**
+//!
+//! With feature `alloc`, `BigRange` steps big integers, which aren't Copy, with big integer len()
+//!
+//!
+//! **Ratio, BigRational**
+//!
+//! `Ratio<i64>` steps exactly by fractions like 1/3, `from_decimals("0", "1", "0.1", true)` builds them from decimal strings,
+//! `BigRange<BigRational>` for any precision
+//!
+//!
+//! **DecimalRange**
+//!
+//! Human entered decimals like "0.05", stepped as scaled integers, yielding f64 or strings with fixed digits
+//!
+//!
+//! **rayon**
+//!
+//! With feature `rayon`, BasicRange and IndexedRange are IntoParallelIterator, split by index so
+//! `zip`, `enumerate` and `collect` keep the order, ranges longer than usize split by `part` for unindexed
+//! methods like `find_any`
+//!
+//!
+//! **no_std**
+//!
+//! Default feature `std` can be turned off for `#![no_std]` targets, ranges and macros only need `core`,
+//! feature `alloc` enables what returns collections, and nothing is printed without `std`
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+/// println! with `std`, type checked but never printed without
+#[cfg(feature = "std")]
+macro_rules! log_print {
+    ($($arg:tt)*) => {
+        std::println!($($arg)*)
+    };
+}
+#[cfg(not(feature = "std"))]
+macro_rules! log_print {
+    ($($arg:tt)*) => {
+        if false {
+            let _ = core::format_args!($($arg)*);
+        }
+    };
+}
+
 /// DocTest for basic_range
 /// ```
 /// use rangex::basic_range::*;
@@ -44,6 +185,86 @@
 /// assert_eq!(s, 5050);
 /// ```
 pub mod basic_range;
+/// DocTest for big_range, with feature `alloc`
+/// ```
+/// use num::bigint::BigUint;
+/// use num::traits::Pow;
+/// use rangex::big_range::BigRange;
+/// // 2^200 through 2^200 + 10^6, step 7
+/// let start = BigUint::from(2u8).pow(200u32);
+/// let end = &start + 1_000_000u32;
+/// let r = BigRange::new(start.clone(), end, 7, true);
+/// assert_eq!(r.len(), BigUint::from(142_858u32));
+/// assert_eq!(r.into_iter().nth(1), Some(start + 7u8));
+/// ```
+#[cfg(feature = "alloc")]
+pub mod big_range;
+/// DocTest for const_range
+/// ```
+/// use rangex::const_range::ConstRange;
+/// // values 0, 3, 6, 9 known at compile time
+/// type R = ConstRange<0, 10, 3, false>;
+/// const VALUES: [i64; R::LEN] = R::to_array();
+/// assert_eq!(VALUES, [0, 3, 6, 9]);
+/// let mut s = 0;
+/// R::for_each(|v| s += v);
+/// assert_eq!(s, 18);
+/// ```
+/// Step 0 does not compile
+/// ```compile_fail
+/// use rangex::const_range::ConstRange;
+/// let _ = ConstRange::<0, 10, 0, false>::LEN;
+/// ```
+pub mod const_range;
+/// DocTest for convert_range
+/// ```
+/// use rangex::basic_range::*;
+/// use rangex::convert_range::*;
+/// let (start, end): (u32, usize) = (1, 10);
+/// let r = BasicRange::<u64>::from_mixed(start, end, 3, true, EndpointPolicy::Error).unwrap();
+/// assert_eq!(r.into_iter().collect::<Vec<_>>(), [1, 4, 7, 10]);
+/// // -5 and 1000 don't fit u8, clamped to 0 and 255
+/// let r = BasicRange::<u8>::from_mixed(-5, 1000, 100, true, EndpointPolicy::Clamp).unwrap();
+/// assert_eq!(r.len(), 3);
+/// assert_eq!(
+///     BasicRange::<u8>::from_mixed(-5, 1000, 100, true, EndpointPolicy::Error).unwrap_err(),
+///     OutOfRangeError::Start
+/// );
+/// // same progression as u32 and f64
+/// let r = BasicRange::<u64>::new(0, 1000, 3, true);
+/// assert_eq!(r.try_cast::<u32>().unwrap().len(), 334);
+/// assert_eq!(r.cast_lossy::<f64>().unwrap().value_at(333), Some(999.0));
+/// ```
+pub mod convert_range;
+/// DocTest for date_range, with feature `chrono`
+/// ```
+/// # #[cfg(feature = "chrono")]
+/// # {
+/// use chrono::NaiveDate;
+/// use rangex::basic_range::*;
+/// use rangex::date_range::*;
+/// let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+/// // every other Monday in January 2024
+/// let mondays = BasicRange::new(date(2024, 1, 1), date(2024, 1, 31), 14, true);
+/// assert_eq!(mondays.len(), 3);
+/// // every month end from 2023-01 through 2026-10
+/// let month_ends = MonthRange::new(date(2023, 1, 31), date(2026, 10, 31), 1, true, MonthDay::KeepMonthEnd);
+/// assert_eq!(month_ends.value_at(1), Some(date(2023, 2, 28)));
+/// # }
+/// ```
+#[cfg(feature = "chrono")]
+pub mod date_range;
+/// DocTest for decimal_range
+/// ```
+/// use rangex::decimal_range::DecimalRange;
+/// // from 0.05 to 2.5 step 0.05, exactly 50 values
+/// let r = DecimalRange::parse("0.05", "2.5", "0.05", true).unwrap();
+/// assert_eq!(r.len(), 50);
+/// assert_eq!(r.to_f64s().nth(2), Some(0.15));
+/// # #[cfg(feature = "alloc")]
+/// assert_eq!(r.to_strings().last(), Some("2.50".to_string()));
+/// ```
+pub mod decimal_range;
 /// DocTest for indexed_range
 /// ```
 /// use rangex::indexed_range::*;
@@ -61,5 +282,107 @@ pub mod basic_range;
 /// assert_eq!(sv, 5050);
 /// ```
 pub mod indexed_range;
+/// DocTest for ip_range
+/// ```
+/// use rangex::basic_range::*;
+/// use rangex::ip_range::Ipv4Cidr;
+/// use std::net::Ipv4Addr;
+/// // every 4th address in 10.0.0.0/22
+/// let block: Ipv4Cidr = "10.0.0.0/22".parse().unwrap();
+/// assert_eq!(block.addresses(4).len(), 256);
+/// // hosts from 192.168.1.10 through .200
+/// let hosts = BasicRange::new(Ipv4Addr::new(192, 168, 1, 10), Ipv4Addr::new(192, 168, 1, 200), 1, true);
+/// assert_eq!(hosts.len(), 191);
+/// // /24s inside a /16
+/// let block: Ipv4Cidr = "172.16.0.0/16".parse().unwrap();
+/// assert_eq!(block.subnets(24).unwrap().count(), 256);
+/// ```
+pub mod ip_range;
+/// DocTest for par_range, with feature `rayon`
+/// ```
+/// # #[cfg(feature = "rayon")]
+/// # {
+/// use rangex::basic_range::*;
+/// use rangex::indexed_range::IndexedRange;
+/// use rayon::prelude::*;
+/// let r = BasicRange::<u64>::new(0, 1_000_000, 7, false);
+/// let squares: Vec<u64> = r.into_par_iter().map(|v| v * v).collect();
+/// assert_eq!(squares[3], 441);
+/// let indexed: Vec<(usize, u64)> = IndexedRange::<u64>::new(0, 100, 7, false).into_par_iter().collect();
+/// assert_eq!(indexed[3], (3, 21));
+/// # }
+/// ```
+#[cfg(feature = "rayon")]
+pub mod par_range;
+/// DocTest for ratio_range
+/// ```
+/// use num::rational::Ratio;
+/// use rangex::basic_range::*;
+/// // 0 through 1 by exactly 0.1, 11 values without float drift
+/// let r = BasicRange::<Ratio<i64>>::from_decimals("0", "1", "0.1", true).unwrap();
+/// assert_eq!(r.len(), 11);
+/// assert_eq!(r.into_iter().last(), Some(Ratio::from_integer(1)));
+/// // by 1/3
+/// let r = BasicRange::new(Ratio::from_integer(0), Ratio::from_integer(1), Ratio::new(1, 3), false);
+/// assert_eq!(r.into_iter().collect::<Vec<Ratio<i64>>>(), [Ratio::new(0, 1), Ratio::new(1, 3), Ratio::new(2, 3)]);
+/// ```
+pub mod ratio_range;
+/// DocTest for slice_range
+/// ```
+/// use rangex::basic_range::*;
+/// let r = BasicRange::<u16>::new(0, 30, 2, false);
+/// let (head, tail) = r.split_at(3);
+/// assert_eq!(head.into_iter().collect::<Vec<_>>(), [0, 2, 4]);
+/// assert_eq!(tail.value_at(0), Some(6));
+/// // Python's r[10:2:-3]
+/// assert_eq!(r.sub(Some(10), Some(2), -3).into_iter().collect::<Vec<_>>(), [20, 14, 8]);
+/// assert_eq!(r.clamp_to(5, 11).len(), 3);
+/// ```
+pub mod slice_range;
+/// DocTest for split_range
+/// ```
+/// use rangex::basic_range::*;
+/// use rangex::range_inclusive;
+/// // batches of 1000 as (first, last)
+/// let batches: Vec<_> = range_inclusive!(u64, 1, 2500, 1)
+///     .chunks(1000)
+///     .map(|batch| (batch.first().unwrap(), batch.last().unwrap()))
+///     .collect();
+/// assert_eq!(batches, [(1, 1000), (1001, 2000), (2001, 2500)]);
+/// // share of worker 3 of 12
+/// let r = range_inclusive!(u64, 1, 100, 1);
+/// assert_eq!(r.part_indices(3, 12), 27..36);
+/// assert_eq!(r.part(3, 12).first(), Some(28));
+/// ```
+pub mod split_range;
+/// DocTest for time_range
+/// ```
+/// # #[cfg(feature = "std")]
+/// # {
+/// use rangex::basic_range::*;
+/// use std::time::{Duration, UNIX_EPOCH};
+/// // every 15 minutes of the hour after 2023-11-14 22:13:20 UTC, on quarter hours since UNIX_EPOCH
+/// let step = Duration::from_secs(15 * 60);
+/// let start = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+/// let quarters = BasicRange::aligned(start, start + Duration::from_secs(3600), step, false);
+/// assert_eq!(quarters.len(), 4);
+/// assert_eq!(quarters.first(), Some(start + Duration::from_secs(100)));
+/// let r = BasicRange::by_duration(Duration::ZERO, Duration::from_secs(3600), step, true);
+/// assert_eq!(r.len(), 5);
+/// # }
+/// ```
+pub mod time_range;
+/// DocTest for transform_range
+/// ```
+/// use rangex::basic_range::*;
+/// // pixels 0..4 as byte offsets x * 4 + 1000
+/// let pixels = BasicRange::<u32>::new(0, 4, 1, false);
+/// let bytes = pixels.affine(4, 1000).unwrap();
+/// assert_eq!(bytes.into_iter().collect::<Vec<_>>(), [1000, 1004, 1008, 1012]);
+/// assert!(bytes.contains(1008) && !bytes.contains(1009));
+/// assert_eq!(bytes.reversed().value_at(0), Some(1012));
+/// assert!(pixels.shift(-1).is_err());
+/// ```
+pub mod transform_range;
 
 mod edge;
@@ -0,0 +1,482 @@
+use crate::basic_range::*;
+use crate::indexed_range::IndexedRange;
+use rayon::iter::plumbing::{
+    bridge, bridge_unindexed, Consumer, Folder, Producer, ProducerCallback, UnindexedConsumer,
+    UnindexedProducer,
+};
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+/// Values for rayon, split by index so zip(), enumerate() and collect() keep the order,
+/// ranges longer than usize are only unindexed, e.g. find_any(), and len() panics for them
+#[derive(Clone, Copy, Debug)]
+pub struct ParRange<T>
+where
+    T: IteratorOps,
+{
+    range: BasicRange<T>,
+}
+
+/// Same as ParRange, with the indices IndexedRange yields
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedParRange<T>
+where
+    T: IteratorOps,
+{
+    range: BasicRange<T>,
+    first_index: usize,
+    index_step: usize,
+}
+
+impl<T> IntoParallelIterator for BasicRange<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    type Iter = ParRange<T>;
+    type Item = T;
+
+    fn into_par_iter(self) -> ParRange<T> {
+        ParRange { range: self }
+    }
+}
+
+impl<T> IntoParallelIterator for IndexedRange<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    type Iter = IndexedParRange<T>;
+    type Item = (usize, T);
+
+    fn into_par_iter(self) -> IndexedParRange<T> {
+        IndexedParRange {
+            range: self.basic_range,
+            first_index: self.first_index,
+            index_step: self.index_step,
+        }
+    }
+}
+
+/// ParallelIterator and IndexedParallelIterator for ParRange and IndexedParRange
+macro_rules! impl_par_range {
+    ($($par:ident => $producer:ident, $item:ty);* $(;)?) => {$(
+        impl<T> ParallelIterator for $par<T>
+        where
+            T: IteratorOps + Send,
+            T::Step: Send,
+            T::ExtendedStep: Send,
+        {
+            type Item = $item;
+
+            fn drive_unindexed<C>(self, consumer: C) -> C::Result
+            where
+                C: UnindexedConsumer<$item>,
+            {
+                if self.range.checked_len().is_some() {
+                    bridge(self, consumer)
+                } else {
+                    self.drive_wide(consumer)
+                }
+            }
+
+            fn opt_len(&self) -> Option<usize> {
+                self.range.checked_len()
+            }
+        }
+
+        impl<T> IndexedParallelIterator for $par<T>
+        where
+            T: IteratorOps + Send,
+            T::Step: Send,
+            T::ExtendedStep: Send,
+        {
+            fn len(&self) -> usize {
+                self.range
+                    .checked_len()
+                    .expect("len beyond usize, only unindexed methods of ranges this long")
+            }
+
+            fn drive<C>(self, consumer: C) -> C::Result
+            where
+                C: Consumer<$item>,
+            {
+                bridge(self, consumer)
+            }
+
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: ProducerCallback<$item>,
+            {
+                callback.callback($producer::from(self))
+            }
+        }
+    )*};
+}
+
+impl_par_range! {
+    ParRange => RangeProducer, T;
+    IndexedParRange => IndexedRangeProducer, (usize, T);
+}
+
+impl<T> ParRange<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    /// Ranges longer than usize, split in halves by part()
+    fn drive_wide<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<T>,
+    {
+        bridge_unindexed(WideRangeProducer { range: self.range }, consumer)
+    }
+}
+
+impl<T> IndexedParRange<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    /// Indices are usize, so len() panics for ranges longer than usize
+    fn drive_wide<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<(usize, T)>,
+    {
+        bridge(self, consumer)
+    }
+}
+
+/// Values of a range longer than usize, split in halves until rayon stops
+#[derive(Clone, Copy, Debug)]
+pub struct WideRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    range: BasicRange<T>,
+}
+
+impl<T> UnindexedProducer for WideRangeProducer<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    type Item = T;
+
+    fn split(self) -> (Self, Option<Self>) {
+        if self.range.checked_len().is_some_and(|len| len < 2) {
+            return (self, None);
+        }
+        (
+            WideRangeProducer {
+                range: self.range.part(0, 2),
+            },
+            Some(WideRangeProducer {
+                range: self.range.part(1, 2),
+            }),
+        )
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<T>,
+    {
+        folder.consume_iter(self.range)
+    }
+}
+
+/// Values at indices front..back of a range, split in halves by rayon
+#[derive(Clone, Copy, Debug)]
+pub struct RangeProducer<T>
+where
+    T: IteratorOps,
+{
+    range: BasicRange<T>,
+    front: usize,
+    back: usize,
+}
+
+impl<T> From<ParRange<T>> for RangeProducer<T>
+where
+    T: IteratorOps,
+{
+    fn from(par: ParRange<T>) -> Self {
+        RangeProducer {
+            range: par.range,
+            front: 0,
+            back: par
+                .range
+                .checked_len()
+                .expect("len beyond usize, only unindexed methods of ranges this long"),
+        }
+    }
+}
+
+impl<T> RangeProducer<T>
+where
+    T: IteratorOps,
+{
+    /// Value at *index*, below len() of the range
+    fn value(&self, index: usize) -> T {
+        self.range
+            .value_at_unchecked(T::ExtendedStep::from_usize(index))
+    }
+
+    fn split(self, index: usize) -> (Self, Self) {
+        let middle = self.front + index;
+        (
+            RangeProducer {
+                back: middle,
+                ..self
+            },
+            RangeProducer {
+                front: middle,
+                ..self
+            },
+        )
+    }
+}
+
+impl<T> Iterator for RangeProducer<T>
+where
+    T: IteratorOps,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.value(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.back - self.front;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T> DoubleEndedIterator for RangeProducer<T>
+where
+    T: IteratorOps,
+{
+    fn next_back(&mut self) -> Option<T> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.value(self.back))
+    }
+}
+
+impl<T> ExactSizeIterator for RangeProducer<T> where T: IteratorOps {}
+
+impl<T> Producer for RangeProducer<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    type Item = T;
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        self.split(index)
+    }
+}
+
+/// RangeProducer yielding indices too
+#[derive(Clone, Copy, Debug)]
+pub struct IndexedRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    values: RangeProducer<T>,
+    first_index: usize,
+    index_step: usize,
+}
+
+impl<T> From<IndexedParRange<T>> for IndexedRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    fn from(par: IndexedParRange<T>) -> Self {
+        IndexedRangeProducer {
+            values: RangeProducer::from(ParRange { range: par.range }),
+            first_index: par.first_index,
+            index_step: par.index_step,
+        }
+    }
+}
+
+impl<T> IndexedRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    /// Index IndexedRange yields with the value at *index* of the range
+    fn index(&self, index: usize) -> usize {
+        self.first_index + index * self.index_step
+    }
+}
+
+impl<T> Iterator for IndexedRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        let index = self.index(self.values.front);
+        self.values.next().map(|value| (index, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.values.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IndexedRangeProducer<T>
+where
+    T: IteratorOps,
+{
+    fn next_back(&mut self) -> Option<(usize, T)> {
+        let value = self.values.next_back()?;
+        Some((self.index(self.values.back), value))
+    }
+}
+
+impl<T> ExactSizeIterator for IndexedRangeProducer<T> where T: IteratorOps {}
+
+impl<T> Producer for IndexedRangeProducer<T>
+where
+    T: IteratorOps + Send,
+    T::Step: Send,
+    T::ExtendedStep: Send,
+{
+    type Item = (usize, T);
+    type IntoIter = Self;
+
+    fn into_iter(self) -> Self {
+        self
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        let (left, right) = self.values.split(index);
+        (
+            IndexedRangeProducer {
+                values: left,
+                ..self
+            },
+            IndexedRangeProducer {
+                values: right,
+                ..self
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    #[test]
+    fn par_range() {
+        for (start, end, step) in [(0, 1000, 3), (1000, -1000, -7), (5, 5, 1), (5, 0, 1)] {
+            for inclusive in [false, true] {
+                let r = BasicRange::<i32>::new(start, end, step, inclusive);
+                let expect: Vec<i32> = r.into_iter().collect();
+                let values: Vec<i32> = r.into_par_iter().collect();
+                assert_eq!(values, expect);
+                assert_eq!(r.into_par_iter().len(), expect.len());
+                assert_eq!(
+                    r.into_par_iter().map(i64::from).sum::<i64>(),
+                    expect.iter().map(|&v| v as i64).sum::<i64>()
+                );
+            }
+        }
+
+        // zip and enumerate keep the order
+        let a = BasicRange::<u8>::new(0, 255, 5, true);
+        let b = BasicRange::<u64>::new(1000, 0, -10, false);
+        let zipped: Vec<(u8, u64)> = a.into_par_iter().zip(b.into_par_iter()).collect();
+        assert_eq!(zipped, a.into_iter().zip(b).collect::<Vec<_>>());
+        let enumerated: Vec<(usize, u8)> = a.into_par_iter().enumerate().collect();
+        assert_eq!(enumerated[51], (51, 255));
+        let reversed: Vec<u8> = a.into_par_iter().rev().collect();
+        assert_eq!(reversed[0], 255);
+
+        // Wide and float ranges
+        let r = BasicRange::<u128>::new(u128::MAX, 0, i128::MIN + 1, true);
+        let values: Vec<u128> = r.into_par_iter().collect();
+        assert_eq!(values, [u128::MAX, 1 << 127, 1]);
+        let r = BasicRange::<f64>::new(0.0, 10.0, 0.5, true);
+        let values: Vec<f64> = r.into_par_iter().collect();
+        assert_eq!(values, r.into_iter().collect::<Vec<_>>());
+        // Steps not exact in binary, the same values bit for bit
+        for r in [
+            BasicRange::<f64>::new(0.0, 10.0, 0.1, true),
+            BasicRange::<f64>::new(0.0, 10.0, 0.1, false).part(1, 3),
+            BasicRange::<f64>::new(5.0, -5.0, -0.3, true).reversed(),
+        ] {
+            let values: Vec<f64> = r.into_par_iter().collect();
+            assert_eq!(values, r.into_iter().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn wide_par_range() {
+        let r = BasicRange::<u64>::new(0, u64::MAX, 1, true);
+        assert_eq!(r.into_par_iter().opt_len(), None);
+        assert_eq!(r.into_par_iter().find_first(|&v| v > 1000), Some(1001));
+        assert!(r.into_par_iter().any(|v| v == 12_345));
+        let r = BasicRange::<i128>::new(i128::MAX, i128::MIN, -3, true);
+        assert_eq!(r.into_par_iter().opt_len(), None);
+        assert_eq!(
+            r.into_par_iter().find_first(|&v| v % 1000 == 0),
+            r.into_iter().find(|&v| v % 1000 == 0)
+        );
+
+        // Longest range that still fits
+        let r = BasicRange::<u64>::new(1, u64::MAX, 1, true);
+        assert_eq!(r.into_par_iter().opt_len(), Some(usize::MAX));
+        assert_eq!(r.into_par_iter().len(), usize::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "len beyond usize")]
+    fn wide_par_range_len() {
+        BasicRange::<u64>::new(0, u64::MAX, 1, true)
+            .into_par_iter()
+            .len();
+    }
+
+    #[test]
+    fn indexed_par_range() {
+        let r = IndexedRange::<u32>::new(0, 100_000, 7, false);
+        let expect: Vec<(usize, u32)> = IndexedRange::<u32>::new(0, 100_000, 7, false)
+            .into_iter()
+            .collect();
+        let values: Vec<(usize, u32)> = r.into_par_iter().collect();
+        assert_eq!(values, expect);
+
+        // Parts keep the indices of the whole range
+        let r = IndexedRange::<i16>::new(-100, 100, 3, true);
+        let part: Vec<(usize, i16)> = r.interleave_part(2, 5).into_par_iter().collect();
+        assert_eq!(
+            part,
+            r.interleave_part(2, 5).into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!(part[1], (7, -79));
+        let last = r.part(3, 4).into_par_iter().rev().collect::<Vec<_>>()[0];
+        assert_eq!(last, (66, 98));
+    }
+}
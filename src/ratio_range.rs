@@ -0,0 +1,243 @@
+use crate::basic_range::*;
+use core::fmt;
+use num::rational::Ratio;
+use num::{CheckedAdd, CheckedMul, Integer, Signed, Zero};
+
+/// Exact rational steps, e.g. 1/3, computed in Ratio of the wider integer so there is no drift
+macro_rules! impl_ratio_ops {
+    ($($t:ty => $extended:ty);* $(;)?) => {$(
+        impl StepOps for Ratio<$t> {
+            fn min() -> Self {
+                Ratio::from_integer(<$t>::MIN)
+            }
+            fn max() -> Self {
+                Ratio::from_integer(<$t>::MAX)
+            }
+            fn floor(self) -> Self {
+                Ratio::floor(&self)
+            }
+            fn round(self) -> Self {
+                Ratio::round(&self)
+            }
+            fn to_usize(self) -> usize {
+                self.to_integer() as usize
+            }
+            fn from_usize(value: usize) -> Self {
+                Ratio::from_integer(value as $t)
+            }
+        }
+
+        impl IteratorOps for Ratio<$t> {
+            type Step = Ratio<$t>;
+            type ExtendedStep = Ratio<$extended>;
+
+            fn min() -> Self {
+                Ratio::from_integer(<$t>::MIN)
+            }
+            fn max() -> Self {
+                Ratio::from_integer(<$t>::MAX)
+            }
+
+            fn to_step(self) -> Self::Step {
+                self
+            }
+            fn from_step(step: Self::Step) -> Self {
+                step
+            }
+
+            fn to_extended_step(self) -> Self::ExtendedStep {
+                Ratio::new_raw(*self.numer() as $extended, *self.denom() as $extended)
+            }
+            /// Panics if numerator or denominator doesn't fit, rationals don't wrap
+            fn from_extended_step(extended_step: Self::ExtendedStep) -> Self {
+                let numer = <$t>::try_from(*extended_step.numer());
+                let denom = <$t>::try_from(*extended_step.denom());
+                match (numer, denom) {
+                    (Ok(numer), Ok(denom)) => Ratio::new_raw(numer, denom),
+                    _ => panic!("{} overflows Ratio<{}>", extended_step, stringify!($t)),
+                }
+            }
+            fn extend_step(step: Self::Step) -> Self::ExtendedStep {
+                step.to_extended_step()
+            }
+        }
+    )*};
+}
+
+impl_ratio_ops! {
+    i32 => i64;
+    i64 => i128;
+}
+
+impl StepOps for Ratio<i128> {
+    fn min() -> Self {
+        Ratio::from_integer(i128::MIN)
+    }
+    fn max() -> Self {
+        Ratio::from_integer(i128::MAX)
+    }
+    fn floor(self) -> Self {
+        Ratio::floor(&self)
+    }
+    fn round(self) -> Self {
+        Ratio::round(&self)
+    }
+    fn to_usize(self) -> usize {
+        self.to_integer() as usize
+    }
+    fn from_usize(value: usize) -> Self {
+        Ratio::from_integer(value as i128)
+    }
+}
+
+/// Why a decimal string like "-0.05" can't be a Ratio
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseDecimalError {
+    /// Not digits with an optional sign and decimal point
+    Invalid,
+    /// Too many digits for the integer type
+    Overflow,
+    /// More fractional digits than the range has
+    TooManyDigits,
+    /// Step of a range is zero
+    ZeroStep,
+}
+
+impl fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseDecimalError::Invalid => "invalid decimal",
+            ParseDecimalError::Overflow => "decimal overflows",
+            ParseDecimalError::TooManyDigits => "too many fractional digits",
+            ParseDecimalError::ZeroStep => "step is zero",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseDecimalError {}
+
+/// Exact Ratio of a decimal string, "0.1" is 1/10, for Ratio<i32>, Ratio<i64> and BigRational
+pub fn decimal_ratio<T>(s: &str) -> Result<Ratio<T>, ParseDecimalError>
+where
+    T: Integer + Signed + Clone + CheckedMul + CheckedAdd + From<u8>,
+{
+    let (negative, digits) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(ParseDecimalError::Invalid);
+    }
+    let ten = T::from(10);
+    let (mut numer, mut denom) = (T::zero(), T::one());
+    for (index, c) in integer.chars().chain(fraction.chars()).enumerate() {
+        let digit = c.to_digit(10).ok_or(ParseDecimalError::Invalid)?;
+        numer = numer
+            .checked_mul(&ten)
+            .and_then(|n| n.checked_add(&T::from(digit as u8)))
+            .ok_or(ParseDecimalError::Overflow)?;
+        if index >= integer.len() {
+            denom = denom.checked_mul(&ten).ok_or(ParseDecimalError::Overflow)?;
+        }
+    }
+    let ratio = Ratio::new(numer, denom);
+    Ok(if negative { -ratio } else { ratio })
+}
+
+impl BasicRange<Ratio<i64>> {
+    /// Range of exact decimals, e.g. `from_decimals("0", "1", "0.1", true)` has 11 values
+    pub fn from_decimals(
+        start: &str,
+        end: &str,
+        step: &str,
+        inclusive: bool,
+    ) -> Result<Self, ParseDecimalError> {
+        let step = decimal_ratio(step)?;
+        if step.is_zero() {
+            return Err(ParseDecimalError::ZeroStep);
+        }
+        Ok(BasicRange::new(
+            decimal_ratio(start)?,
+            decimal_ratio(end)?,
+            step,
+            inclusive,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    fn r(numer: i64, denom: i64) -> Ratio<i64> {
+        Ratio::new(numer, denom)
+    }
+
+    #[test]
+    fn ratio_range() {
+        let third = r(1, 3);
+        let values: Vec<_> = BasicRange::new(r(0, 1), r(1, 1), third, true)
+            .into_iter()
+            .collect();
+        assert_eq!(values, [r(0, 1), r(1, 3), r(2, 3), r(1, 1)]);
+        let range = BasicRange::new(r(0, 1), r(1, 1), third, false);
+        assert_eq!(range.len(), 3);
+        assert_eq!(range.into_iter().last(), Some(r(2, 3)));
+
+        // End not on step
+        let range = BasicRange::new(r(0, 1), r(1, 1), r(3, 10), false);
+        assert!(!range.invalid_range && range.inclusive_or_not_on_step);
+        assert_eq!(range.len(), 4);
+        assert_eq!(
+            range.into_iter().collect::<Vec<_>>(),
+            [r(0, 1), r(3, 10), r(3, 5), r(9, 10)]
+        );
+        let range = BasicRange::new(r(1, 1), r(-1, 1), r(-1, 2), true);
+        assert_eq!(range.len(), 5);
+        assert!(range.contains(r(-1, 2)) && !range.contains(r(1, 4)) && !range.contains(r(-3, 2)));
+        assert_eq!(range.value_at(3), Some(r(-1, 2)));
+
+        // 1000 steps of 1/7 land exactly on 1000/7
+        let range = BasicRange::new(r(0, 1), r(1000, 7), r(1, 7), true);
+        assert_eq!(range.len(), 1001);
+        assert_eq!(range.into_iter().last(), Some(r(1000, 7)));
+        let values: Vec<_> =
+            BasicRange::new(Ratio::new(1, 2), Ratio::new(2, 1), Ratio::new(1, 2), true)
+                .into_iter()
+                .collect::<Vec<Ratio<i32>>>();
+        assert_eq!(values.len(), 4);
+    }
+
+    #[test]
+    fn decimals() {
+        assert_eq!(decimal_ratio("0.1"), Ok(r(1, 10)));
+        assert_eq!(decimal_ratio("-2.50"), Ok(r(-5, 2)));
+        assert_eq!(decimal_ratio("+.5"), Ok(r(1, 2)));
+        assert_eq!(decimal_ratio("7."), Ok(r(7, 1)));
+        assert_eq!(decimal_ratio::<i64>("."), Err(ParseDecimalError::Invalid));
+        assert_eq!(decimal_ratio::<i64>("1e3"), Err(ParseDecimalError::Invalid));
+        assert_eq!(
+            decimal_ratio::<i64>("1.2.3"),
+            Err(ParseDecimalError::Invalid)
+        );
+        assert_eq!(
+            decimal_ratio::<i32>("0.0000000001"),
+            Err(ParseDecimalError::Overflow)
+        );
+
+        let range = BasicRange::from_decimals("0", "1", "0.1", true).unwrap();
+        assert_eq!(range.len(), 11);
+        assert_eq!(range.value_at(3), Some(r(3, 10)));
+        assert_eq!(range.into_iter().last(), Some(r(1, 1)));
+        let range = BasicRange::from_decimals("0.05", "2.5", "0.05", true).unwrap();
+        assert_eq!(range.len(), 50);
+        assert!(BasicRange::from_decimals("0", "x", "0.1", true).is_err());
+        assert_eq!(
+            BasicRange::from_decimals("0", "1", "0.0", true).unwrap_err(),
+            ParseDecimalError::ZeroStep
+        );
+    }
+}
@@ -0,0 +1,272 @@
+use crate::basic_range::*;
+use num::{One, Zero};
+
+impl<T> BasicRange<T>
+where
+    T: IteratorOps,
+{
+    /// First *n* values, all if there are fewer
+    pub fn take(&self, n: usize) -> Self {
+        self.slice(
+            T::ExtendedStep::zero(),
+            self.clamped_index(n),
+            T::ExtendedStep::one(),
+        )
+    }
+
+    /// Values after the first *n*, empty if there are fewer
+    pub fn skip(&self, n: usize) -> Self {
+        let n = self.clamped_index(n);
+        self.slice(n, self.len_extended() - n, T::ExtendedStep::one())
+    }
+
+    /// First *index* values and the rest, panics if index is beyond len() like slice::split_at
+    pub fn split_at(&self, index: usize) -> (Self, Self) {
+        let len = self.len_extended();
+        assert!(
+            self.checked_len().is_none_or(|len| index <= len),
+            "index {} beyond len {}",
+            index,
+            len
+        );
+        let at = T::ExtendedStep::from_usize(index);
+        let one = T::ExtendedStep::one();
+        (
+            self.slice(T::ExtendedStep::zero(), at, one),
+            self.slice(at, len - at, one),
+        )
+    }
+
+    /// Python style slice of the values, `r.sub(Some(2), Some(10), 3)` is `r[2:10:3]`,
+    /// negative indices count from the end and a negative step goes backward, e.g. `r[::-1]`
+    pub fn sub(&self, start: Option<isize>, stop: Option<isize>, step: isize) -> Self {
+        assert!(step != 0, "Step can't be 0");
+        let (zero, one) = (T::ExtendedStep::zero(), T::ExtendedStep::one());
+        let len = self.len_extended();
+        // Magnitudes past len + 1 clamp the same way, and may not fit small step types
+        let magnitude = |value: isize| match len.checked_to_usize() {
+            Some(len_usize) if value.unsigned_abs() > len_usize => len + one,
+            _ => T::ExtendedStep::from_usize(value.unsigned_abs()),
+        };
+        let (lower, upper) = if step > 0 {
+            (zero, len)
+        } else {
+            (zero - one, len - one)
+        };
+        let index = |value: Option<isize>, default| match value {
+            None => default,
+            Some(value) if value < 0 => {
+                let index = len - magnitude(value);
+                if index < lower {
+                    lower
+                } else {
+                    index
+                }
+            }
+            Some(value) => {
+                let index = magnitude(value);
+                if index > upper {
+                    upper
+                } else {
+                    index
+                }
+            }
+        };
+        let (first, distance) = if step > 0 {
+            let first = index(start, lower);
+            (first, index(stop, upper) - first)
+        } else {
+            let first = index(start, upper);
+            (first, first - index(stop, lower))
+        };
+        let stride = magnitude(step);
+        if distance <= zero {
+            return self.slice(zero, zero, one);
+        }
+        let count = ((distance - one) / stride).floor() + one;
+        let stride = if step > 0 { stride } else { zero - stride };
+        self.slice(first, count, stride)
+    }
+
+    /// Values between *low* and *high*, both inclusive, e.g. `[0, 3, 6, 9]` clamped to 2..=8 is `[3, 6]`
+    pub fn clamp_to(&self, low: T, high: T) -> Self {
+        let zero = T::ExtendedStep::zero();
+        let one = T::ExtendedStep::one();
+        let len = self.len_extended();
+        if len == zero || low > high {
+            return self.slice(zero, zero, one);
+        }
+        let step = self.extended_step.abs();
+        let start = self.start.to_extended_step();
+        // Distance from start to the values nearest start and farthest from it
+        let (near, far) = if self.extended_step > zero {
+            (
+                low.to_extended_step() - start,
+                high.to_extended_step() - start,
+            )
+        } else {
+            (
+                start - high.to_extended_step(),
+                start - low.to_extended_step(),
+            )
+        };
+        if far < zero {
+            return self.slice(zero, zero, one);
+        }
+        let first = if near <= zero {
+            zero
+        } else {
+            let steps = (near / step).floor();
+            if steps * step < near {
+                steps + one
+            } else {
+                steps
+            }
+        };
+        let last_index = len - one;
+        let last = (far / step).floor();
+        let last = if last < last_index { last } else { last_index };
+        if first > last {
+            return self.slice(zero, zero, one);
+        }
+        self.slice(first, last - first + one, one)
+    }
+
+    /// *len* values from the value at index *first*, every *stride*th value,
+    /// backward for a negative stride, empty with the same step and direction for len 0
+    pub(crate) fn slice(
+        &self,
+        first: T::ExtendedStep,
+        len: T::ExtendedStep,
+        stride: T::ExtendedStep,
+    ) -> Self {
+        let zero = T::ExtendedStep::zero();
+        let one = T::ExtendedStep::one();
+        let forward = (self.extended_step > zero) == (stride > zero);
+        let step = if len > one {
+            self.extended_step.abs() * stride.abs()
+        } else {
+            self.extended_step.abs()
+        };
+        if len == zero {
+            return Self::with_magnitude(self.start, self.start, step, forward, false);
+        }
+        let index = self.origin_index + first;
+        if stride == one {
+            // Same progression, float values stay the same bit for bit
+            return Self::from_origin(self.origin, index, self.extended_step, len);
+        }
+        if stride == T::ExtendedStep::negative_one() {
+            return Self::from_origin(self.origin, zero - index, zero - self.extended_step, len);
+        }
+        let step = if forward { step } else { zero - step };
+        Self::from_origin(self.value_at_unchecked(first), zero, step, len)
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    fn values<T: IteratorOps>(range: BasicRange<T>) -> Vec<T> {
+        range.into_iter().collect()
+    }
+
+    #[test]
+    fn take_skip_split() {
+        let r = BasicRange::<u8>::new(0, 20, 3, false);
+        assert_eq!(values(r.take(3)), [0, 3, 6]);
+        assert_eq!(values(r.take(100)), values(r));
+        assert_eq!(values(r.skip(5)), [15, 18]);
+        assert!(r.skip(7).is_empty() && r.skip(100).is_empty());
+        assert_eq!(r.skip(100).extended_step, 3);
+        let (head, tail) = r.split_at(2);
+        assert_eq!(
+            (values(head), values(tail)),
+            (vec![0, 3], vec![6, 9, 12, 15, 18])
+        );
+        let (head, tail) = r.split_at(7);
+        assert_eq!((head.len(), tail.len()), (7, 0));
+
+        let r = BasicRange::<i32>::new(10, -10, -4, true);
+        assert_eq!(values(r.take(2)), [10, 6]);
+        assert_eq!(values(r.skip(4)), [-6, -10]);
+        assert!(r.skip(1).contains(-6) && !r.skip(1).contains(10));
+        assert_eq!(r.skip(2).value_at(0), Some(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "beyond len")]
+    fn split_beyond_len() {
+        BasicRange::<u8>::new(0, 10, 1, false).split_at(11);
+    }
+
+    #[test]
+    fn sub() {
+        // Same as Python list(range(0, 30, 2))[...]
+        let r = BasicRange::<u16>::new(0, 30, 2, false);
+        assert_eq!(values(r.sub(Some(2), Some(10), 3)), [4, 10, 16]);
+        assert_eq!(values(r.sub(None, None, -1)), values(r.reversed()));
+        assert_eq!(values(r.sub(Some(-3), None, 1)), [24, 26, 28]);
+        assert_eq!(values(r.sub(Some(10), Some(2), -3)), [20, 14, 8]);
+        assert_eq!(values(r.sub(None, Some(-12), -5)), [28, 18, 8]);
+        assert_eq!(values(r.sub(Some(-100), Some(2), 1)), [0, 2]);
+        assert_eq!(values(r.sub(Some(100), None, -7)), [28, 14, 0]);
+        assert!(r.sub(Some(5), Some(5), 1).is_empty());
+        assert!(r.sub(Some(2), Some(5), -1).is_empty());
+
+        let r = BasicRange::<i8>::new(100, -100, -50, true);
+        assert_eq!(values(r.sub(None, None, 2)), [100, 0, -100]);
+        assert_eq!(values(r.sub(None, None, -2)), [-100, 0, 100]);
+        assert_eq!(values(r.sub(Some(1), Some(-1), 1)), [50, 0, -50]);
+    }
+
+    #[test]
+    fn full_width() {
+        let r = BasicRange::<u64>::new(0, u64::MAX, 1, true);
+        assert_eq!(values(r.take(3)), [0, 1, 2]);
+        assert_eq!(r.skip(1).first(), Some(1));
+        assert_eq!(r.skip(1).len_extended(), u64::MAX as i128);
+        let (head, tail) = r.split_at(5);
+        assert_eq!(
+            (head.len(), tail.first(), tail.last()),
+            (5, Some(5), Some(u64::MAX))
+        );
+        assert_eq!(values(r.sub(Some(-2), None, 1)), [u64::MAX - 1, u64::MAX]);
+        assert_eq!(values(r.sub(None, Some(-7), -3)), [u64::MAX, u64::MAX - 3]);
+        assert_eq!(
+            values(r.sub(None, None, 1 << 62).take(5)),
+            [0, 1 << 62, 2 << 62, 3 << 62]
+        );
+        assert_eq!(
+            values(r.clamp_to(u64::MAX - 1, u64::MAX)),
+            [u64::MAX - 1, u64::MAX]
+        );
+
+        let r = BasicRange::<u128>::new(u128::MAX, 0, -1, true);
+        assert_eq!(values(r.take(2)), [u128::MAX, u128::MAX - 1]);
+        assert_eq!(values(r.sub(Some(-1), None, 1)), [0]);
+        assert_eq!(r.reversed().first(), Some(0));
+        assert_eq!(
+            r.skip(usize::MAX).first(),
+            Some(u128::MAX - usize::MAX as u128)
+        );
+    }
+
+    #[test]
+    fn clamp_to() {
+        let r = BasicRange::<u8>::new(0, 10, 3, false);
+        assert_eq!(values(r.clamp_to(2, 8)), [3, 6]);
+        assert_eq!(values(r.clamp_to(0, 255)), [0, 3, 6, 9]);
+        assert_eq!(values(r.clamp_to(3, 3)), [3]);
+        assert!(r.clamp_to(4, 5).is_empty());
+        assert!(r.clamp_to(10, 255).is_empty());
+        assert!(r.clamp_to(8, 2).is_empty());
+
+        let r = BasicRange::<i64>::new(100, -100, -7, true);
+        assert_eq!(values(r.clamp_to(-10, 10)), [9, 2, -5]);
+        assert!(r.clamp_to(101, 200).is_empty() && r.clamp_to(-200, -101).is_empty());
+        let r = BasicRange::<f64>::new(0.0, 2.0, 0.5, true);
+        assert_eq!(values(r.clamp_to(0.2, 1.5)), [0.5, 1.0, 1.5]);
+    }
+}
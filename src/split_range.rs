@@ -0,0 +1,440 @@
+use crate::basic_range::*;
+use core::iter::Enumerate;
+use core::ops::Range;
+use num::{One, Zero};
+
+impl<T> BasicRange<T>
+where
+    T: IteratorOps,
+{
+    /// Pieces of *size* values in order, the last one may be shorter, e.g. batches of 1000,
+    /// panics if size is 0 like slice::chunks
+    pub fn chunks(&self, size: usize) -> Chunks<T> {
+        Chunks::new(*self, size, false)
+    }
+
+    /// Pieces of *size* values from the end, the first one may be shorter,
+    /// each piece still in the range's order like slice::rchunks
+    pub fn rchunks(&self, size: usize) -> Chunks<T> {
+        Chunks::new(*self, size, true)
+    }
+
+    /// Same as chunks(), with the chunk number
+    pub fn indexed_chunks(&self, size: usize) -> Enumerate<Chunks<T>> {
+        self.chunks(size).enumerate()
+    }
+
+    /// Same as rchunks(), with the chunk number
+    pub fn indexed_rchunks(&self, size: usize) -> Enumerate<Chunks<T>> {
+        self.rchunks(size).enumerate()
+    }
+
+    /// Indices of the values in part *i* of *k* balanced parts, earlier parts get one more value
+    /// when len() doesn't divide evenly, panics if i is not below k or an index is beyond usize
+    pub fn part_indices(&self, i: usize, k: usize) -> Range<usize> {
+        let (first, len) = self.part_bounds(i, k);
+        let index = |index: T::ExtendedStep| {
+            index
+                .checked_to_usize()
+                .expect("part index beyond usize, use part()")
+        };
+        index(first)..index(first + len)
+    }
+
+    /// Part *i* of *k* contiguous parts whose lengths differ by at most one, e.g. worker i of k
+    pub fn part(&self, i: usize, k: usize) -> Self {
+        let (first, len) = self.part_bounds(i, k);
+        self.slice(first, len, T::ExtendedStep::one())
+    }
+
+    /// All *k* parts of part()
+    #[cfg(feature = "alloc")]
+    pub fn split_into(&self, k: usize) -> alloc::vec::Vec<Self> {
+        (0..k).map(|i| self.part(i, k)).collect()
+    }
+
+    /// Values at indices i, i + k, i + 2k, ... round robin for worker *i* of *k*,
+    /// start moved i steps and step times k, panics if i is not below k
+    pub fn interleave_part(&self, i: usize, k: usize) -> Self {
+        assert!(i < k, "part {} of {} parts", i, k);
+        let one = T::ExtendedStep::one();
+        let len = self.len_extended();
+        let first = self.clamped_index(i);
+        // k beyond len() leaves one value in the part the same as len() does, and slice()
+        // multiplies step and stride only for two values or more, at most the type's width apart
+        let stride = self.clamped_index(k);
+        let count = if first < len {
+            ((len - first - one) / stride).floor() + one
+        } else {
+            T::ExtendedStep::zero()
+        };
+        self.slice(first, count, stride)
+    }
+
+    /// Index in the whole range of value *local* of interleave_part(i, k), None beyond len()
+    pub fn interleave_index(&self, i: usize, k: usize, local: usize) -> Option<usize> {
+        assert!(i < k, "part {} of {} parts", i, k);
+        local
+            .checked_mul(k)
+            .and_then(|index| index.checked_add(i))
+            .filter(|&index| self.checked_len().is_none_or(|len| index < len))
+    }
+
+    /// Index of the first value and number of values in part *i* of *k*
+    pub(crate) fn part_bounds(&self, i: usize, k: usize) -> (T::ExtendedStep, T::ExtendedStep) {
+        assert!(i < k, "part {} of {} parts", i, k);
+        let (zero, one) = (T::ExtendedStep::zero(), T::ExtendedStep::one());
+        let len = self.len_extended();
+        match len.checked_to_usize() {
+            // More parts than values, each of the first len() parts has one value
+            Some(len) if k > len => (self.clamped_index(i), if i < len { one } else { zero }),
+            // k is at most len(), so it fits T::ExtendedStep
+            _ => {
+                let (i, k) = (
+                    T::ExtendedStep::from_usize(i),
+                    T::ExtendedStep::from_usize(k),
+                );
+                let size = (len / k).floor();
+                let rest = len - size * k;
+                if i < rest {
+                    (i * (size + one), size + one)
+                } else {
+                    (i * size + rest, size)
+                }
+            }
+        }
+    }
+}
+
+/// Pieces of a range as BasicRange, from chunks() or rchunks()
+#[derive(Clone, Copy, Debug)]
+pub struct Chunks<T>
+where
+    T: IteratorOps,
+{
+    range: BasicRange<T>,
+    len: T::ExtendedStep,
+    size: T::ExtendedStep,
+    from_end: bool,
+    /// Chunks not yielded yet are front..back
+    front: T::ExtendedStep,
+    back: T::ExtendedStep,
+}
+
+impl<T> Chunks<T>
+where
+    T: IteratorOps,
+{
+    fn new(range: BasicRange<T>, size: usize, from_end: bool) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+        let (zero, one) = (T::ExtendedStep::zero(), T::ExtendedStep::one());
+        let len = range.len_extended();
+        let size = range.clamped_index(size);
+        Chunks {
+            range,
+            len,
+            size,
+            from_end,
+            front: zero,
+            back: if len == zero {
+                zero
+            } else {
+                ((len - one) / size).floor() + one
+            },
+        }
+    }
+
+    /// Chunk number *index*
+    fn chunk(&self, index: T::ExtendedStep) -> BasicRange<T> {
+        let (first, end) = if self.from_end {
+            let end = self.len - index * self.size;
+            let first = if end > self.size {
+                end - self.size
+            } else {
+                T::ExtendedStep::zero()
+            };
+            (first, end)
+        } else {
+            let first = index * self.size;
+            let end = first + self.size;
+            (first, if end < self.len { end } else { self.len })
+        };
+        self.range.slice(first, end - first, T::ExtendedStep::one())
+    }
+}
+
+impl<T> Iterator for Chunks<T>
+where
+    T: IteratorOps,
+{
+    type Item = BasicRange<T>;
+
+    fn next(&mut self) -> Option<BasicRange<T>> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front = self.front + T::ExtendedStep::one();
+        Some(self.chunk(self.front - T::ExtendedStep::one()))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<BasicRange<T>> {
+        let remaining = self.back - self.front;
+        self.front = match remaining.checked_to_usize() {
+            Some(remaining) if n >= remaining => self.back,
+            _ => self.front + T::ExtendedStep::from_usize(n),
+        };
+        self.next()
+    }
+
+    /// Exact unless there are more than usize::MAX chunks
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match (self.back - self.front).checked_to_usize() {
+            Some(remaining) => (remaining, Some(remaining)),
+            None => (usize::MAX, None),
+        }
+    }
+}
+
+impl<T> DoubleEndedIterator for Chunks<T>
+where
+    T: IteratorOps,
+{
+    fn next_back(&mut self) -> Option<BasicRange<T>> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back = self.back - T::ExtendedStep::one();
+        Some(self.chunk(self.back))
+    }
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    fn values<T: IteratorOps>(chunks: impl Iterator<Item = BasicRange<T>>) -> Vec<Vec<T>> {
+        chunks.map(|chunk| chunk.into_iter().collect()).collect()
+    }
+
+    #[test]
+    fn chunks() {
+        let r = BasicRange::<u64>::new(1, 10, 1, true);
+        assert_eq!(r.chunks(4).count(), 3);
+        assert_eq!(
+            values(r.chunks(4)),
+            [vec![1, 2, 3, 4], vec![5, 6, 7, 8], vec![9, 10]]
+        );
+        assert_eq!(
+            values(r.rchunks(4)),
+            [vec![7, 8, 9, 10], vec![3, 4, 5, 6], vec![1, 2]]
+        );
+        assert_eq!(values(r.chunks(10)), [(1..=10).collect::<Vec<_>>()]);
+        assert_eq!(r.chunks(100).count(), 1);
+        let batches: Vec<_> = r
+            .chunks(3)
+            .map(|chunk| (chunk.first().unwrap(), chunk.last().unwrap()))
+            .collect();
+        assert_eq!(batches, [(1, 3), (4, 6), (7, 9), (10, 10)]);
+
+        // Stepped and backward
+        let r = BasicRange::<i16>::new(20, -20, -6, true);
+        assert_eq!(
+            values(r.chunks(3)),
+            [vec![20, 14, 8], vec![2, -4, -10], vec![-16]]
+        );
+        assert_eq!(
+            values(r.rchunks(5)),
+            [vec![8, 2, -4, -10, -16], vec![20, 14]]
+        );
+        assert_eq!(
+            values(r.chunks(3).rev()),
+            [vec![-16], vec![2, -4, -10], vec![20, 14, 8]]
+        );
+        let mut chunks = r.chunks(2);
+        assert_eq!(chunks.nth(2).map(|c| c.first()), Some(Some(-4)));
+        assert_eq!(chunks.size_hint(), (1, Some(1)));
+        assert!(chunks.nth(5).is_none());
+
+        // Chunks cover the range exactly
+        let r = BasicRange::<u8>::new(0, 255, 7, true);
+        let joined: Vec<u8> = r.chunks(5).flat_map(|c| c.into_iter()).collect();
+        assert_eq!(joined, r.into_iter().collect::<Vec<_>>());
+        assert_eq!(r.chunks(5).map(|c| c.len()).sum::<usize>(), r.len());
+        assert_eq!(BasicRange::<u8>::new(0, 0, 1, false).chunks(5).count(), 0);
+    }
+
+    #[test]
+    fn float_chunks() {
+        // Chunks yield the range's values bit for bit, not a new progression from each start
+        for r in [
+            BasicRange::<f64>::new(0.0, 10.0, 0.1, true),
+            BasicRange::<f64>::new(10.0, -10.0, -0.3, false),
+        ] {
+            let serial: Vec<f64> = r.into_iter().collect();
+            for size in [1, 7, 10, 1000] {
+                let joined: Vec<f64> = r.chunks(size).flat_map(|c| c.into_iter()).collect();
+                assert_eq!(joined, serial);
+                let joined: Vec<f64> = r.rchunks(size).rev().flat_map(|c| c.into_iter()).collect();
+                assert_eq!(joined, serial);
+                for chunk in r.chunks(size) {
+                    assert_eq!(chunk.last(), chunk.into_iter().last());
+                    assert!(chunk.into_iter().all(|value| chunk.contains(value)));
+                }
+            }
+        }
+        let r = BasicRange::<f32>::new(0.0, 100.0, 0.7, true);
+        let joined: Vec<f32> = r.chunks(7).flat_map(|c| c.into_iter()).collect();
+        assert_eq!(joined, r.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn indexed_chunks() {
+        let r = BasicRange::<u32>::new(0, 2500, 1, false);
+        let chunks: Vec<_> = r
+            .indexed_chunks(1000)
+            .map(|(i, chunk)| (i, chunk.first().unwrap(), chunk.len()))
+            .collect();
+        assert_eq!(chunks, [(0, 0, 1000), (1, 1000, 1000), (2, 2000, 500)]);
+        let (i, first) = r
+            .indexed_rchunks(1000)
+            .map(|(i, c)| (i, c.first()))
+            .last()
+            .unwrap();
+        assert_eq!((i, first), (2, Some(0)));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split_into() {
+        let r = BasicRange::<u32>::new(0, 10, 1, false);
+        let parts: Vec<_> = r
+            .split_into(4)
+            .into_iter()
+            .map(|p| p.into_iter().collect::<Vec<_>>())
+            .collect();
+        assert_eq!(
+            parts,
+            [vec![0, 1, 2], vec![3, 4, 5], vec![6, 7], vec![8, 9]]
+        );
+        assert_eq!(r.part_indices(2, 4), 6..8);
+        assert_eq!(r.part(3, 4).first(), Some(8));
+
+        // Stepped and backward, lengths differ by at most one
+        let r = BasicRange::<i64>::new(1000, -1000, -7, true);
+        for k in [1, 2, 5, 12, 286, 300] {
+            let parts = r.split_into(k);
+            assert_eq!(parts.len(), k);
+            let lens: Vec<usize> = parts.iter().map(|p| p.len()).collect();
+            assert!(lens.iter().max().unwrap() - lens.iter().min().unwrap() <= 1);
+            let joined: Vec<i64> = parts.iter().flat_map(|p| p.into_iter()).collect();
+            assert_eq!(joined, r.into_iter().collect::<Vec<_>>());
+            for (i, part) in parts.iter().enumerate() {
+                let indices = r.part_indices(i, k);
+                assert_eq!(part.len(), indices.len());
+                assert_eq!(part.first(), r.value_at(indices.start));
+            }
+        }
+        // More parts than values, trailing parts are empty
+        let parts = BasicRange::<u8>::new(0, 2, 1, true).split_into(5);
+        assert_eq!(
+            parts.iter().map(|p| p.len()).collect::<Vec<_>>(),
+            [1, 1, 1, 0, 0]
+        );
+    }
+
+    #[test]
+    fn interleave_part() {
+        let r = BasicRange::<u32>::new(0, 10, 1, false);
+        let parts: Vec<Vec<u32>> = (0..3)
+            .map(|i| r.interleave_part(i, 3).into_iter().collect())
+            .collect();
+        assert_eq!(parts, [vec![0, 3, 6, 9], vec![1, 4, 7], vec![2, 5, 8]]);
+        assert_eq!(r.interleave_part(1, 3).extended_step, 3);
+        assert_eq!(r.interleave_index(1, 3, 2), Some(7));
+        assert_eq!(r.interleave_index(1, 3, 3), None);
+        assert_eq!(r.interleave_index(2, 3, usize::MAX), None);
+        assert!(r.interleave_part(5, 20).contains(5) && r.interleave_part(5, 20).len() == 1);
+        assert!(r.interleave_part(15, 20).is_empty());
+
+        // Every value in exactly one part, global index from local index
+        let r = BasicRange::<i8>::new(127, -128, -5, true);
+        let k = 4;
+        let mut seen = vec![false; r.len()];
+        for i in 0..k {
+            let part = r.interleave_part(i, k);
+            for (local, value) in part.into_iter().enumerate() {
+                let index = r.interleave_index(i, k, local).unwrap();
+                assert_eq!(r.value_at(index), Some(value));
+                assert!(!seen[index]);
+                seen[index] = true;
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+        // Step times k wider than the step type
+        let r = BasicRange::<u8>::new(0, 255, 100, true);
+        assert_eq!(
+            r.interleave_part(0, 2).into_iter().collect::<Vec<_>>(),
+            [0, 200]
+        );
+    }
+
+    #[test]
+    fn full_width_parts() {
+        let r = BasicRange::<u64>::new(0, u64::MAX, 1, true);
+        let firsts: Vec<_> = r.chunks(1 << 62).map(|c| c.first().unwrap()).collect();
+        assert_eq!(firsts, [0, 1 << 62, 2 << 62, 3 << 62]);
+        assert_eq!(r.chunks(1).size_hint(), (usize::MAX, None));
+        assert_eq!(r.rchunks(10).next().unwrap().first(), Some(u64::MAX - 9));
+        assert_eq!(
+            r.chunks(10).next_back().unwrap().first(),
+            Some(u64::MAX - 5)
+        );
+        assert_eq!(r.part(3, 4).first(), Some(3 << 62));
+        assert_eq!(r.part(3, 4).last(), Some(u64::MAX));
+        assert_eq!(r.part_indices(1, 4), 1 << 62..2 << 62);
+        assert_eq!(r.interleave_part(1, 2).last(), Some(u64::MAX));
+        assert_eq!(r.interleave_index(1, 2, 5), Some(11));
+
+        let r = BasicRange::<u128>::new(0, u128::MAX, 1, true);
+        assert_eq!(r.part(1, 2).first(), Some(1 << 127));
+        assert_eq!(
+            r.part(1, 2).len_extended(),
+            ExtendedI128::from_u128(1 << 127)
+        );
+        assert_eq!(
+            r.chunks(usize::MAX).nth(2).unwrap().first(),
+            Some(2 * usize::MAX as u128)
+        );
+        assert_eq!(r.interleave_part(2, 3).last(), Some(u128::MAX - 1));
+
+        // Step times k beyond the extended step type
+        let r = BasicRange::<u128>::with_magnitude(
+            0,
+            u128::MAX,
+            ExtendedI128::from_u128(u128::MAX),
+            true,
+            true,
+        );
+        let part = |i, k| r.interleave_part(i, k).into_iter().collect::<Vec<_>>();
+        assert_eq!(part(0, 2), [0]);
+        assert_eq!(part(1, 2), [u128::MAX]);
+        assert_eq!(part(1, usize::MAX), [u128::MAX]);
+        let r = BasicRange::<i8>::new(-128, 127, 127, true);
+        assert_eq!(
+            r.interleave_part(0, 2).into_iter().collect::<Vec<_>>(),
+            [-128, 126]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "part 4 of 4 parts")]
+    fn part_beyond_k() {
+        BasicRange::<u8>::new(0, 10, 1, false).part(4, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-zero")]
+    fn zero_chunk_size() {
+        BasicRange::<u8>::new(0, 10, 1, false).chunks(0);
+    }
+}
@@ -0,0 +1,356 @@
+use crate::basic_range::*;
+use core::time::Duration;
+use num::Zero;
+#[cfg(feature = "std")]
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// Durations step by nanoseconds, from ZERO through Duration::MAX
+impl RangeStep for Duration {
+    type Position = u128;
+    fn to_position(self) -> u128 {
+        self.as_nanos()
+    }
+    fn from_position(position: u128) -> Self {
+        Duration::new(
+            (position / NANOS_PER_SEC) as u64,
+            (position % NANOS_PER_SEC) as u32,
+        )
+    }
+    fn first() -> Self {
+        Duration::ZERO
+    }
+    fn last() -> Self {
+        Duration::MAX
+    }
+}
+
+/// Types stepped by a Duration, in exact nanoseconds
+pub trait DurationStep: IteratorOps {
+    /// Step of *step* forward
+    fn duration_step(step: Duration) -> Self::Step;
+}
+
+impl DurationStep for Duration {
+    fn duration_step(step: Duration) -> i128 {
+        step.as_nanos() as i128
+    }
+}
+
+impl<T> BasicRange<T>
+where
+    T: DurationStep,
+{
+    /// Range from *start* to *end* by *step*, backward if end is before start
+    pub fn by_duration(start: T, end: T, step: Duration, inclusive: bool) -> Self {
+        let step = T::duration_step(step);
+        let step = if end < start {
+            T::Step::zero() - step
+        } else {
+            step
+        };
+        BasicRange::new(start, end, step, inclusive)
+    }
+}
+
+/// SystemTimes step by nanoseconds since UNIX_EPOCH, as i128 that holds any SystemTime,
+/// first() and last() are the earliest and latest SystemTime of the platform
+#[cfg(feature = "std")]
+impl RangeStep for SystemTime {
+    type Position = i128;
+    fn to_position(self) -> i128 {
+        match self.duration_since(UNIX_EPOCH) {
+            Ok(after) => after.as_nanos() as i128,
+            Err(before) => -(before.duration().as_nanos() as i128),
+        }
+    }
+    fn from_position(position: i128) -> Self {
+        let offset = Duration::from_position(position.unsigned_abs());
+        if position < 0 {
+            UNIX_EPOCH - offset
+        } else {
+            UNIX_EPOCH + offset
+        }
+    }
+    fn first() -> Self {
+        system_time_bounds().0
+    }
+    fn last() -> Self {
+        system_time_bounds().1
+    }
+}
+
+/// Earliest and latest SystemTime, searched once as platforms differ
+#[cfg(feature = "std")]
+fn system_time_bounds() -> (SystemTime, SystemTime) {
+    static BOUNDS: std::sync::OnceLock<(SystemTime, SystemTime)> = std::sync::OnceLock::new();
+    *BOUNDS.get_or_init(|| {
+        (
+            farthest_from_epoch(|offset| UNIX_EPOCH.checked_sub(offset)),
+            farthest_from_epoch(|offset| UNIX_EPOCH.checked_add(offset)),
+        )
+    })
+}
+
+/// SystemTime of the longest offset *shift* accepts, by binary search of nanoseconds
+#[cfg(feature = "std")]
+fn farthest_from_epoch(shift: impl Fn(Duration) -> Option<SystemTime>) -> SystemTime {
+    let (mut low, mut high) = (0, Duration::MAX.as_nanos());
+    if let Some(time) = shift(Duration::MAX) {
+        return time;
+    }
+    while high - low > 1 {
+        let middle = low + (high - low) / 2;
+        if shift(Duration::from_position(middle)).is_some() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    shift(Duration::from_position(low)).expect("SystemTime of a shorter offset")
+}
+
+#[cfg(feature = "std")]
+impl DurationStep for SystemTime {
+    fn duration_step(step: Duration) -> i128 {
+        nanos_step(step)
+    }
+}
+
+#[cfg(feature = "std")]
+impl BasicRange<SystemTime> {
+    /// Same as by_duration(), but starts at the first multiple of *step* since UNIX_EPOCH
+    /// not before *start* going forward, or not after *start* going backward,
+    /// e.g. 10:07 by 15 minutes to 11:00 yields 10:15, 10:30, 10:45 and 11:00 if inclusive,
+    /// panics for a zero step like new()
+    pub fn aligned(start: SystemTime, end: SystemTime, step: Duration, inclusive: bool) -> Self {
+        // Direction of start and end, aligned start may pass end and leave the range empty
+        let (aligned_start, step) = if end < start {
+            (floor_to_step(start, step), -nanos_step(step))
+        } else {
+            (ceil_to_step(start, step), nanos_step(step))
+        };
+        BasicRange::new(aligned_start, end, step, inclusive)
+    }
+}
+
+/// Step in nanoseconds, panics for a zero step before it's used as a divisor
+#[cfg(feature = "std")]
+fn nanos_step(step: Duration) -> i128 {
+    assert!(!step.is_zero(), "Step can't be 0");
+    step.as_nanos() as i128
+}
+
+/// Latest multiple of *step* since UNIX_EPOCH not after *time*
+#[cfg(feature = "std")]
+pub fn floor_to_step(time: SystemTime, step: Duration) -> SystemTime {
+    let position = time.to_position();
+    SystemTime::from_position(position - position.rem_euclid(nanos_step(step)))
+}
+
+/// Earliest multiple of *step* since UNIX_EPOCH not before *time*
+#[cfg(feature = "std")]
+pub fn ceil_to_step(time: SystemTime, step: Duration) -> SystemTime {
+    let floor = floor_to_step(time, step);
+    if floor == time {
+        time
+    } else {
+        SystemTime::from_position(floor.to_position() + nanos_step(step))
+    }
+}
+
+/// Range of Instants by a Duration step, backward if end is before start
+///
+/// Instants have no epoch, so values are offsets from *start* stepped as a BasicRange<Duration>.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug)]
+pub struct InstantRange {
+    pub start: Instant,
+    pub offsets: BasicRange<Duration>,
+    pub backward: bool,
+}
+
+#[cfg(feature = "std")]
+impl InstantRange {
+    pub fn new(start: Instant, end: Instant, step: Duration, inclusive: bool) -> Self {
+        let backward = end < start;
+        let distance = if backward {
+            start.duration_since(end)
+        } else {
+            end.duration_since(start)
+        };
+        InstantRange {
+            start,
+            offsets: BasicRange::by_duration(Duration::ZERO, distance, step, inclusive),
+            backward,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl IntoIterator for InstantRange {
+    type Item = Instant;
+    type IntoIter = InstantRangeIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        InstantRangeIter {
+            start: self.start,
+            offsets: self.offsets.into_iter(),
+            backward: self.backward,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct InstantRangeIter {
+    start: Instant,
+    offsets: BasicRangeIter<Duration>,
+    backward: bool,
+}
+
+#[cfg(feature = "std")]
+impl Iterator for InstantRangeIter {
+    type Item = Instant;
+
+    fn next(&mut self) -> Option<Instant> {
+        let offset = self.offsets.next()?;
+        Some(if self.backward {
+            self.start - offset
+        } else {
+            self.start + offset
+        })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod main_test {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    fn minutes(values: &[u32]) -> Vec<Duration> {
+        values.iter().map(|m| MINUTE * *m).collect()
+    }
+
+    fn at(secs: i64) -> SystemTime {
+        SystemTime::from_position(secs as i128 * 1_000_000_000)
+    }
+
+    #[test]
+    fn duration_range() {
+        let r = BasicRange::by_duration(Duration::ZERO, 60 * MINUTE, 15 * MINUTE, true);
+        assert_eq!(r.len(), 5);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            minutes(&[0, 15, 30, 45, 60])
+        );
+        let r = BasicRange::by_duration(Duration::ZERO, 60 * MINUTE, 15 * MINUTE, false);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), minutes(&[0, 15, 30, 45]));
+        let r = BasicRange::by_duration(50 * MINUTE, 10 * MINUTE, 15 * MINUTE, true);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), minutes(&[50, 35, 20]));
+
+        // Exact nanoseconds, no float drift
+        let third = Duration::from_nanos(333_333_333);
+        let r = BasicRange::by_duration(Duration::ZERO, Duration::from_secs(1000), third, true);
+        assert_eq!(r.len(), 3001);
+        assert_eq!(r.into_iter().last(), Some(Duration::new(999, 999_999_000)));
+        let near_max = Duration::MAX - Duration::from_nanos(2);
+        let r: Vec<_> = BasicRange::new(near_max, Duration::MAX, 1, true)
+            .into_iter()
+            .collect();
+        assert_eq!(
+            r,
+            [
+                near_max,
+                Duration::MAX - Duration::from_nanos(1),
+                Duration::MAX
+            ]
+        );
+    }
+
+    #[test]
+    fn system_time_range() {
+        let r = BasicRange::by_duration(at(0), at(3600), 15 * MINUTE, false);
+        assert_eq!(r.len(), 4);
+        assert_eq!(r.value_at(3), Some(at(2700)));
+        let r = BasicRange::by_duration(at(3600), at(0), 20 * MINUTE, true);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            [at(3600), at(2400), at(1200), at(0)]
+        );
+        let before_epoch = BasicRange::by_duration(at(-90), at(90), MINUTE, true);
+        assert_eq!(
+            before_epoch.into_iter().collect::<Vec<_>>(),
+            [at(-90), at(-30), at(30), at(90)]
+        );
+        let time = UNIX_EPOCH + Duration::new(1_700_000_000, 123_456_789);
+        assert_eq!(SystemTime::from_position(time.to_position()), time);
+
+        // Beyond i64 nanoseconds, years 1677 through 2262
+        let year = 365 * 24 * 3600;
+        let r = BasicRange::by_duration(
+            at(-400 * year),
+            at(400 * year),
+            Duration::from_secs(200 * year as u64),
+            true,
+        );
+        assert_eq!(r.len(), 5);
+        assert_eq!(r.last(), Some(at(400 * year)));
+        assert!(r.contains(at(-200 * year)));
+        assert_eq!(
+            at(-400 * year).to_position(),
+            -400 * year as i128 * 1_000_000_000
+        );
+    }
+
+    #[test]
+    fn aligned() {
+        assert_eq!(floor_to_step(at(607), 5 * MINUTE), at(600));
+        assert_eq!(ceil_to_step(at(607), 5 * MINUTE), at(900));
+        assert_eq!(ceil_to_step(at(900), 5 * MINUTE), at(900));
+        assert_eq!(floor_to_step(at(-1), MINUTE), at(-60));
+
+        let step = 15 * MINUTE;
+        let r = BasicRange::aligned(at(36_420), at(39_600), step, true);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            [at(36_900), at(37_800), at(38_700), at(39_600)]
+        );
+        let r = BasicRange::aligned(at(39_599), at(36_000), step, false);
+        assert_eq!(
+            r.into_iter().collect::<Vec<_>>(),
+            [at(38_700), at(37_800), at(36_900)]
+        );
+        assert!(BasicRange::aligned(at(61), at(119), MINUTE, true).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Step can't be 0")]
+    fn aligned_zero_step() {
+        BasicRange::aligned(at(0), at(60), Duration::ZERO, true);
+    }
+
+    #[test]
+    fn instant_range() {
+        let start = Instant::now();
+        let end = start + 10 * MINUTE;
+        let r = InstantRange::new(start, end, 3 * MINUTE, true);
+        assert_eq!(r.len(), 4);
+        let values: Vec<_> = r.into_iter().collect();
+        assert_eq!(values[3], start + 9 * MINUTE);
+        let values: Vec<_> = InstantRange::new(end, start, 5 * MINUTE, false)
+            .into_iter()
+            .collect();
+        assert_eq!(values, [end, end - 5 * MINUTE]);
+    }
+}
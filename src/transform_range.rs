@@ -0,0 +1,178 @@
+use crate::basic_range::*;
+use crate::convert_range::OutOfRangeError;
+use num::{One, Zero};
+
+impl<T> BasicRange<T>
+where
+    T: IteratorOps,
+{
+    /// Same values plus *offset*, e.g. `[0, 3, 6]` shifted by 10 is `[10, 13, 16]`
+    pub fn shift(&self, offset: T::ExtendedStep) -> Result<Self, OutOfRangeError> {
+        self.affine(T::ExtendedStep::one(), offset)
+    }
+
+    /// Same values times *factor*, a negative factor reverses direction
+    pub fn scale(&self, factor: T::ExtendedStep) -> Result<Self, OutOfRangeError> {
+        self.affine(factor, T::ExtendedStep::zero())
+    }
+
+    /// Same values negated, e.g. `[1, 2, 3]` is `[-1, -2, -3]`
+    pub fn negate(&self) -> Result<Self, OutOfRangeError> {
+        self.affine(T::ExtendedStep::negative_one(), T::ExtendedStep::zero())
+    }
+
+    /// Values `x * a + b`, e.g. pixel indices to byte offsets `x * 4 + base`,
+    /// error if a value doesn't fit T, or if *a* is 0 with more than one value
+    pub fn affine(&self, a: T::ExtendedStep, b: T::ExtendedStep) -> Result<Self, OutOfRangeError> {
+        let last = match self.last() {
+            Some(last) => last,
+            None => return Ok(*self),
+        };
+        let first =
+            affine_value::<T>(self.start.to_extended_step(), a, b).ok_or(OutOfRangeError::Start)?;
+        let single = last == self.start;
+        let last = affine_value::<T>(last.to_extended_step(), a, b).ok_or(OutOfRangeError::End)?;
+        let step = if single {
+            // Single value, any step
+            T::ExtendedStep::one()
+        } else {
+            affine_value::<T>(self.extended_step, a, T::ExtendedStep::zero())
+                .ok_or(OutOfRangeError::Step)?
+        };
+        Self::through(first, last, step)
+    }
+
+    /// Same values backward, from the last value to start
+    pub fn reversed(&self) -> Self {
+        match self.last() {
+            Some(last) if last != self.start => {
+                let magnitude = self.extended_step.abs();
+                let forward = self.extended_step < T::ExtendedStep::zero();
+                Self::with_magnitude(last, self.start, magnitude, forward, true)
+            }
+            _ => *self,
+        }
+    }
+
+    /// Range from *first* through *last*, all extended, error if they don't fit T
+    fn through(
+        first: T::ExtendedStep,
+        last: T::ExtendedStep,
+        step: T::ExtendedStep,
+    ) -> Result<Self, OutOfRangeError> {
+        let in_bounds =
+            |value| T::min().to_extended_step() <= value && value <= T::max().to_extended_step();
+        if !in_bounds(first) {
+            return Err(OutOfRangeError::Start);
+        }
+        if !in_bounds(last) {
+            return Err(OutOfRangeError::End);
+        }
+        let magnitude = step.abs();
+        if step == T::ExtendedStep::zero() || magnitude > width::<T>() {
+            return Err(OutOfRangeError::Step);
+        }
+        Ok(Self::with_magnitude(
+            T::from_extended_step(first),
+            T::from_extended_step(last),
+            magnitude,
+            step > T::ExtendedStep::zero(),
+            true,
+        ))
+    }
+}
+
+/// max - min of T
+fn width<T: IteratorOps>() -> T::ExtendedStep {
+    T::max().to_extended_step() - T::min().to_extended_step()
+}
+
+/// *x* * *a* + *b*, None if it can't be within width of T, checked before it could overflow
+fn affine_value<T: IteratorOps>(
+    x: T::ExtendedStep,
+    a: T::ExtendedStep,
+    b: T::ExtendedStep,
+) -> Option<T::ExtendedStep> {
+    let width = width::<T>();
+    // Not abs(), which overflows for i128::MIN
+    let within = |value| T::ExtendedStep::zero() - width <= value && value <= width;
+    if !within(b) {
+        return None;
+    }
+    if !within(a) {
+        return (x == T::ExtendedStep::zero()).then_some(b);
+    }
+    if a != T::ExtendedStep::zero() && x.abs() > width / a.abs() {
+        return None;
+    }
+    Some(x * a + b)
+}
+
+#[cfg(test)]
+mod main_test {
+    use super::*;
+
+    fn values<T: IteratorOps>(range: BasicRange<T>) -> Vec<T> {
+        range.into_iter().collect()
+    }
+
+    #[test]
+    fn shift_scale() {
+        let r = BasicRange::<u32>::new(0, 10, 3, false);
+        assert_eq!(values(r.shift(10).unwrap()), [10, 13, 16, 19]);
+        assert_eq!(r.shift(-1).unwrap_err(), OutOfRangeError::Start);
+        let pixels = BasicRange::<u32>::new(0, 4, 1, false);
+        let bytes = pixels.affine(4, 1000).unwrap();
+        assert_eq!(values(bytes), [1000, 1004, 1008, 1012]);
+        assert!(bytes.contains(1008) && !bytes.contains(1009));
+        assert_eq!(bytes.len(), 4);
+
+        let r = BasicRange::<i8>::new(-3, 3, 2, true);
+        assert_eq!(values(r.scale(10).unwrap()), [-30, -10, 10, 30]);
+        assert_eq!(values(r.scale(-10).unwrap()), [30, 10, -10, -30]);
+        assert_eq!(r.scale(50).unwrap_err(), OutOfRangeError::Start);
+        assert_eq!(r.scale(0).unwrap_err(), OutOfRangeError::Step);
+        assert_eq!(values(r.negate().unwrap()), [3, 1, -1, -3]);
+        assert_eq!(
+            BasicRange::<i8>::new(-128, 0, 64, true)
+                .negate()
+                .unwrap_err(),
+            OutOfRangeError::Start
+        );
+        assert_eq!(
+            values(BasicRange::<u8>::new(5, 5, 1, true).scale(0).unwrap()),
+            [0]
+        );
+        // Huge factors are checked before they overflow
+        let r = BasicRange::<u64>::new(u64::MAX, u64::MAX - 2, -1, true);
+        assert_eq!(r.scale(i128::MAX).unwrap_err(), OutOfRangeError::Start);
+        assert_eq!(r.shift(i128::MIN).unwrap_err(), OutOfRangeError::Start);
+        let r = BasicRange::<u128>::new(0, 10, 5, true);
+        assert_eq!(
+            values(
+                r.affine(ExtendedI128::from_i128(-1), ExtendedI128::from_i128(10))
+                    .unwrap()
+            ),
+            [10, 5, 0]
+        );
+
+        // Empty ranges stay empty
+        let empty = BasicRange::<u8>::new(0, 0, 1, false);
+        assert!(empty.shift(300).unwrap().is_empty());
+        let floats = BasicRange::<f64>::new(0.0, 1.0, 0.5, true);
+        assert_eq!(values(floats.affine(2.0, 1.0).unwrap()), [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn reversed() {
+        let r = BasicRange::<u8>::new(0, 10, 3, true);
+        let reversed = r.reversed();
+        assert_eq!(values(reversed), [9, 6, 3, 0]);
+        assert_eq!(values(reversed.reversed()), [0, 3, 6, 9]);
+        let r = BasicRange::<i16>::new(i16::MAX, i16::MIN, i16::MIN, true);
+        assert_eq!(values(r.reversed()), [-1, i16::MAX]);
+        let r = BasicRange::<u8>::with_magnitude(0, 255, 200, true, true);
+        assert_eq!(values(r.reversed()), [200, 0]);
+        assert!(BasicRange::<u8>::new(3, 0, 1, true).reversed().is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+use rangex::basic_range::*;
+use rangex::indexed_range::*;
+use rangex::{indexed_range_inclusive, range_exclusive, range_inclusive, rangex};
+use rangex_derive::RangeStep;
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, RangeStep)]
+struct UserId(u64);
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, RangeStep)]
+struct Offset {
+    bytes: i32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, RangeStep)]
+enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, RangeStep)]
+enum Priority {
+    Low = 1,
+    Mid = 5,
+    High = 10,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, RangeStep)]
+#[range_step(discriminant)]
+#[repr(u8)]
+enum Opcode {
+    Halt = 0xF0,
+    Load = 0x10,
+    Store = 0x20,
+    Add = 0x30,
+}
+
+#[test]
+fn newtype() {
+    let ids: Vec<UserId> = range_inclusive!(UserId, UserId(1), UserId(10), 3)
+        .into_iter()
+        .collect();
+    assert_eq!(ids, [UserId(1), UserId(4), UserId(7), UserId(10)]);
+
+    let offsets: Vec<Offset> = rangex!(Offset { bytes: 8 } downto Offset { bytes: -8 } by 8)
+        .into_iter()
+        .collect();
+    assert_eq!(
+        offsets,
+        [
+            Offset { bytes: 8 },
+            Offset { bytes: 0 },
+            Offset { bytes: -8 }
+        ]
+    );
+}
+
+#[test]
+fn enum_declaration_order() {
+    use Weekday::*;
+    let days: Vec<Weekday> = range_inclusive!(Weekday, Mon, Fri, 2).into_iter().collect();
+    assert_eq!(days, [Mon, Wed, Fri]);
+
+    let days: Vec<Weekday> = range_exclusive!(Weekday, Sun, Mon, -2)
+        .into_iter()
+        .collect();
+    assert_eq!(days, [Sun, Fri, Wed]);
+
+    let all: Vec<(usize, Weekday)> = indexed_range_inclusive!(Weekday, Mon, Sun)
+        .into_iter()
+        .collect();
+    assert_eq!(all.len(), 7);
+    assert_eq!(all[6], (6, Sun));
+
+    assert_eq!(BasicRange::new(Mon, Sun, 3, true).len(), 3);
+    assert_eq!(Sun.wrapping_next(1), Mon);
+    assert_eq!(Thu.to_extended_step() - Mon.to_extended_step(), 3);
+    let days: Vec<Weekday> = range_inclusive!(Weekday, Mon, Sun, 15)
+        .into_iter()
+        .collect();
+    assert_eq!(days, [Mon]);
+    let days: Vec<Weekday> = range_inclusive!(Weekday, Sun, Mon, -20)
+        .into_iter()
+        .collect();
+    assert_eq!(days, [Sun]);
+
+    // Explicit discriminants ascending in declaration order step by declaration order
+    let priorities: Vec<Priority> = range_inclusive!(Priority, Priority::High, Priority::Low, -1)
+        .into_iter()
+        .collect();
+    assert_eq!(priorities, [Priority::High, Priority::Mid, Priority::Low]);
+}
+
+#[test]
+fn enum_discriminant() {
+    use Opcode::*;
+    assert_eq!(Opcode::first(), Load);
+    assert_eq!(Opcode::last(), Halt);
+    let ops: Vec<Opcode> = range_inclusive!(Opcode, Load, Halt, 1)
+        .into_iter()
+        .collect();
+    assert_eq!(ops, [Load, Store, Add, Halt]);
+    let ops: Vec<Opcode> = range_inclusive!(Opcode, Halt, Load, -2)
+        .into_iter()
+        .collect();
+    assert_eq!(ops, [Halt, Store]);
+    assert!(BasicRange::new(Load, Add, 2, true).contains(Add));
+    assert_eq!((Halt.to_position(), Store.to_position()), (3, 1));
+    assert_eq!(Opcode::from_position(2), Add);
+}